@@ -1,20 +1,31 @@
 //use crate::easing_functions::*;
+use crate::evaluation::EvalBreakdown;
 use crate::game_logic::*;
+use crate::log;
+use crate::logging::{Category, Level};
+use crate::rules::with_contest_rules;
+use crate::search_stats::SearchStats;
 use std::collections::HashSet;
-use std::time::SystemTime;
+use std::time::Instant;
+
+// The day a simulated state lands on once the WAIT of day 23 ended the game
+pub const GAME_OVER_DAY: i32 = 24;
 
 pub fn get_estimated_sunpoint_rate(game_state: &GameState) -> f32 {
   let mut total_gathered_sun = 0.0;
 
   for i in 1..=6 {
-    let shadows = get_maxed_out_shadows_in_field(
-      game_state
-        .my_trees
-        .iter()
-        .chain(game_state.opponent_trees.iter()),
-      game_state.day + i,
-      &game_state.cells,
-    );
+    let shadows = with_contest_rules(|rules| {
+      get_maxed_out_shadows_in_field(
+        game_state
+          .my_trees
+          .iter()
+          .chain(game_state.opponent_trees.iter()),
+        game_state.day + i,
+        &game_state.cells,
+        rules,
+      )
+    });
 
     let total_gathered = game_state
       .my_trees
@@ -96,19 +107,42 @@ pub fn get_sunpoint_rate(game_state: &GameState) -> i32 {
     .fold(0, |a, b| a + b);
 }
 
+pub fn get_sunpoint_rate_for_enemy(game_state: &GameState) -> i32 {
+  let shadows = get_shadows_in_field(
+    game_state
+      .my_trees
+      .iter()
+      .chain(game_state.opponent_trees.iter()),
+    game_state.day + 1,
+    &game_state.cells,
+  );
+
+  return game_state
+    .opponent_trees
+    .iter()
+    .map(|tree| {
+      if tree.size > shadows[tree.cell_index as usize] {
+        return tree.size;
+      } else {
+        return 0;
+      }
+    })
+    .fold(0, |a, b| a + b);
+}
+
 pub fn get_richness_score(game_state: &GameState) -> i32 {
   return game_state
     .my_trees
     .iter()
-    .map(|tree| (tree.cell.richness + 3))
+    .map(|tree| tree.cell.richness + 3)
     .fold(0, |a, b| a + b);
 }
 
-pub fn evaluate_state(game_state: &GameState, print_calculations: bool) -> f32 {
+pub fn evaluate_state(game_state: &GameState) -> EvalBreakdown {
   let sunpoint_rate = get_estimated_sunpoint_rate(game_state);
   let enemy_sunpoint_rate = get_estimated_sunpoint_rate_for_enemy(game_state);
 
-  let game_completion_factor = (game_state.day as f32 / 23.0).powf(3.0);
+  let game_completion_factor = (game_state.day.min(23) as f32 / 23.0).powf(3.0);
   let score_valuation = (1.0 + game_state.score as f32).powf(game_completion_factor);
   //let enemy_score_valuation = (1.0 + game_state.opponent_score as f32).powf(game_completion_factor);
   let sunrate_valuation = (1.0 + sunpoint_rate).powf((1 as f32) - game_completion_factor);
@@ -116,29 +150,115 @@ pub fn evaluate_state(game_state: &GameState, print_calculations: bool) -> f32 {
     (1.0 + enemy_sunpoint_rate).powf((1 as f32) - game_completion_factor);
   let richness_score = get_richness_score(game_state) as f32;
 
-  let scp = score_valuation;
-  let srp = sunrate_valuation - (enemy_sunrate_valuation * (1.0 - game_completion_factor));
-
-  let state_value = scp * srp + (richness_score / 10000.0) + (game_state.sunpoints as f32 / 1000.0);
-
-  if print_calculations {
-    eprintln!(
-      "sr: {}, esr: {}, sc: {}, esc: {}, srv: {}, esrv: {}, sp: {}, rs:{}, scp: {}, srp:{}, t:{}",
-      sunpoint_rate,
-      enemy_sunpoint_rate,
-      game_state.score,
-      game_state.opponent_score,
-      sunrate_valuation,
-      enemy_sunrate_valuation,
-      game_state.sunpoints,
-      richness_score,
-      scp,
-      srp,
-      state_value
-    );
+  let mut breakdown = EvalBreakdown::new();
+  breakdown.score_term = score_valuation;
+  breakdown.sunrate_term = sunrate_valuation;
+  breakdown.enemy_term = enemy_sunrate_valuation * (1.0 - game_completion_factor);
+  breakdown.richness_term = richness_score / 10000.0;
+  breakdown.spare_sun_term = game_state.sunpoints as f32 / 1000.0;
+  breakdown.total = breakdown.score_term * (breakdown.sunrate_term - breakdown.enemy_term)
+    + breakdown.richness_term
+    + breakdown.spare_sun_term;
+
+  return breakdown;
+}
+
+// The size 3 trees a player can still complete, the richest cells first. A plan is scored
+// after its WAIT: once that WAIT ended the game there is no turn left to complete them in,
+// and until then only as many as the sun gathered until the end pays for count.
+pub fn get_pending_completions(trees: &Vec<Tree>, sun_budget: i32, day: i32) -> Vec<Cell> {
+  if day >= GAME_OVER_DAY {
+    return Vec::new();
+  }
+
+  let mut cells: Vec<Cell> = trees
+    .iter()
+    .filter(|tree| tree.size == 3)
+    .map(|tree| tree.cell)
+    .collect();
+  cells.sort_by_key(|cell| -cell.richness);
+  cells.truncate((sun_budget.max(0) / 4) as usize);
+
+  return cells;
+}
+
+// Points of the pending completions of both players, taking turns on the nutrients they
+// share: each completion leaves one nutrient less for the next, whoever's it is
+pub fn get_pending_completion_points(
+  nutrients: i32,
+  pending_completions: &Vec<Cell>,
+  enemy_pending_completions: &Vec<Cell>,
+) -> (i32, i32) {
+  let mut nutrients_left = nutrients;
+  let mut points = 0;
+  let mut enemy_points = 0;
+
+  for i in 0..pending_completions
+    .len()
+    .max(enemy_pending_completions.len())
+  {
+    if let Some(cell) = pending_completions.get(i) {
+      points += get_score_for_cell(nutrients_left, cell);
+      nutrients_left = (nutrients_left - 1).max(0);
+    }
+    if let Some(cell) = enemy_pending_completions.get(i) {
+      enemy_points += get_score_for_cell(nutrients_left, cell);
+      nutrients_left = (nutrients_left - 1).max(0);
+    }
   }
 
-  return state_value;
+  return (points, enemy_points);
+}
+
+// Points a player would end the game with by completing their pending completions and
+// turning the rest of the sun they gather until day 23 into points
+pub fn get_projected_final_points(
+  score: i32,
+  sun_budget: i32,
+  number_of_pending_completions: i32,
+  pending_completion_points: i32,
+) -> f32 {
+  let sunpoints_left = (sun_budget - 4 * number_of_pending_completions).max(0);
+
+  return (score + pending_completion_points) as f32 + sunpoints_left as f32 / 3.0;
+}
+
+// Projected final margin in points, positive when we are ahead
+pub fn evaluate_state_by_margin(game_state: &GameState) -> EvalBreakdown {
+  let remaining_days = (23 - game_state.day).max(0);
+  let sun_budget = game_state.sunpoints + get_sunpoint_rate(game_state) * remaining_days;
+  let enemy_sun_budget =
+    game_state.opponent_sunpoints + get_sunpoint_rate_for_enemy(game_state) * remaining_days;
+
+  let pending_completions =
+    get_pending_completions(&game_state.my_trees, sun_budget, game_state.day);
+  let enemy_pending_completions =
+    get_pending_completions(&game_state.opponent_trees, enemy_sun_budget, game_state.day);
+  let (pending_completion_points, enemy_pending_completion_points) = get_pending_completion_points(
+    game_state.nutrients,
+    &pending_completions,
+    &enemy_pending_completions,
+  );
+
+  let projected_points = get_projected_final_points(
+    game_state.score,
+    sun_budget,
+    pending_completions.len() as i32,
+    pending_completion_points,
+  );
+  let enemy_projected_points = get_projected_final_points(
+    game_state.opponent_score,
+    enemy_sun_budget,
+    enemy_pending_completions.len() as i32,
+    enemy_pending_completion_points,
+  );
+
+  let mut breakdown = EvalBreakdown::new();
+  breakdown.score_term = projected_points;
+  breakdown.enemy_term = enemy_projected_points;
+  breakdown.total = projected_points - enemy_projected_points;
+
+  return breakdown;
 }
 
 pub fn simulate_action(game_state: &GameState, action: Action) -> GameState {
@@ -146,13 +266,18 @@ pub fn simulate_action(game_state: &GameState, action: Action) -> GameState {
     Action::Wait => {
       let mut new_game_state = game_state.clone();
 
-      // todo same for enemy
-      for mut tree in new_game_state.my_trees.iter_mut() {
+      for mut tree in new_game_state
+        .my_trees
+        .iter_mut()
+        .chain(new_game_state.opponent_trees.iter_mut())
+      {
         tree.is_dormant = false;
       }
 
       new_game_state.sunpoints += get_sunpoint_rate(&new_game_state);
-      if new_game_state.day < 23 {
+      new_game_state.opponent_sunpoints += get_sunpoint_rate_for_enemy(&new_game_state);
+      new_game_state.opponent_is_waiting = false;
+      if new_game_state.day < GAME_OVER_DAY {
         new_game_state.day += 1;
       }
       // increase day nu
@@ -217,7 +342,33 @@ pub fn simulate_action(game_state: &GameState, action: Action) -> GameState {
   };
 }
 
-pub fn get_next_action(game_state: GameState, seedable_cells: &Vec<Vec<Vec<i32>>>) -> Action {
+pub fn get_next_action(
+  game_state: GameState,
+  seedable_cells: &Vec<Vec<Vec<i32>>>,
+  stats: &mut SearchStats,
+) -> Action {
+  return get_next_action_with_evaluator(game_state, seedable_cells, evaluate_state, stats);
+}
+
+pub fn get_next_action_by_margin(
+  game_state: GameState,
+  seedable_cells: &Vec<Vec<Vec<i32>>>,
+  stats: &mut SearchStats,
+) -> Action {
+  return get_next_action_with_evaluator(
+    game_state,
+    seedable_cells,
+    evaluate_state_by_margin,
+    stats,
+  );
+}
+
+pub fn get_next_action_with_evaluator(
+  game_state: GameState,
+  seedable_cells: &Vec<Vec<Vec<i32>>>,
+  evaluate_state: fn(&GameState) -> EvalBreakdown,
+  stats: &mut SearchStats,
+) -> Action {
   let mut chosen_plan = &ListOfActionsForDay {
     game_state: game_state.clone(),
     actions: vec![Action::Wait],
@@ -226,15 +377,29 @@ pub fn get_next_action(game_state: GameState, seedable_cells: &Vec<Vec<Vec<i32>>
   let mut current_score = f32::MIN;
   //eprintln!("Evaluated score for action WAIT: {}", current_score);
 
+  let generation_start = Instant::now();
   let possible_list_of_actions = get_possible_actions_for_day(&game_state, seedable_cells);
+  stats.generation_time += generation_start.elapsed();
+  stats.nodes_expanded += possible_list_of_actions.len() as u64;
+  for list_of_actions in possible_list_of_actions.iter() {
+    stats.record_depth(list_of_actions.actions.len() as i32);
+  }
 
-  eprintln!(
+  log!(
+    Category::Search,
+    Level::Info,
     "Number of possible actions for day {}: {}",
     game_state.day,
     possible_list_of_actions.len()
   );
 
+  let evaluation_start = Instant::now();
   for (i, list_of_actions) in possible_list_of_actions.iter().enumerate() {
+    // only reported, cutting the loop short would make the chosen plan depend on the clock
+    if !stats.deadline_hit && stats.is_past_deadline() {
+      stats.deadline_hit = true;
+    }
+
     let should_print = i < 1
       || match list_of_actions.actions.first().unwrap() {
         Action::Wait => true,
@@ -246,17 +411,33 @@ pub fn get_next_action(game_state: GameState, seedable_cells: &Vec<Vec<Vec<i32>>
         .iter()
         .map(|action| action_to_order(*action))
         .collect();
-      eprintln!("{}", action_orders.join(" "));
+      log!(
+        Category::Search,
+        Level::Debug,
+        "{}",
+        action_orders.join(" ")
+      );
+    }
+    let breakdown = evaluate_state(&list_of_actions.game_state);
+    stats.states_evaluated += 1;
+    if should_print {
+      log!(Category::Eval, Level::Debug, "{}", breakdown);
     }
-    let new_state_score = evaluate_state(&list_of_actions.game_state, should_print);
+    let new_state_score = breakdown.total;
 
     if new_state_score >= current_score {
       chosen_plan = list_of_actions;
       current_score = new_state_score;
     }
   }
+  stats.evaluation_time += evaluation_start.elapsed();
 
-  evaluate_state(&chosen_plan.game_state, true);
+  log!(
+    Category::Eval,
+    Level::Info,
+    "{}",
+    evaluate_state(&chosen_plan.game_state)
+  );
 
   *chosen_plan.actions.first().unwrap()
 }
@@ -1,9 +1,17 @@
 //use crate::easing_functions::*;
+use crate::evaluation::EvalBreakdown;
 use crate::game_logic::*;
+use crate::log;
+use crate::logging::{Category, Level};
+use crate::rules::with_contest_rules;
+use crate::search_stats::SearchStats;
+use std::time::Instant;
 
 pub fn get_sun_cost_to_score_ratio(target_tree: &Tree, game_state: &GameState) -> f32 {
   return get_score_for_cell(game_state.nutrients, &target_tree.cell) as f32
-    / get_sun_cost_to_completion(target_tree, &game_state.my_trees) as f32;
+    / with_contest_rules(|rules| {
+      get_sun_cost_to_completion(target_tree, &game_state.my_trees, rules)
+    }) as f32;
 }
 
 pub fn get_sunpoint_rate(game_state: &GameState) -> i32 {
@@ -23,7 +31,7 @@ pub fn get_sunpoint_rate(game_state: &GameState) -> i32 {
     .fold(0, |a, b| a + b);
 }
 
-pub fn evaluate_state(game_state: &GameState) -> f32 {
+pub fn evaluate_state(game_state: &GameState) -> EvalBreakdown {
   let sun_cost_to_score_ratio = if game_state.my_trees.len() > 0 {
     (game_state
       .my_trees
@@ -40,31 +48,13 @@ pub fn evaluate_state(game_state: &GameState) -> f32 {
 
   let game_completion_factor = (game_state.day as f32 / 23.0).powf(3.0);
 
-  eprintln!(
-    "Game completion percentage: {}, game completion factor: {}",
-    game_state.day as f32 / 23.0,
-    game_completion_factor
-  );
-  /*eprintln!(
-      "Sunpoint rate: {}, sun to score ratio: {}, score: {}, game completion %: {}, points for score: {}, points for sunrate: {}",
-      normalized_sunpoint_rate,
-      sun_cost_to_score_ratio,
-      game_state.score,
-      game_completion_factor,
-      game_state.score as f32 * game_completion_factor,
-      (normalized_sunpoint_rate as f32) * ((1 as f32) - game_completion_factor)
-  );*/
-
-  let score_valuation = (1.0 + game_state.score as f32).powf(game_completion_factor);
-  let sunrate_valuation =
+  let mut breakdown = EvalBreakdown::new();
+  breakdown.score_term = (1.0 + game_state.score as f32).powf(game_completion_factor);
+  breakdown.sunrate_term =
     (1.0 + normalized_sunpoint_rate as f32).powf((1 as f32) - game_completion_factor);
+  breakdown.total = breakdown.score_term * breakdown.sunrate_term;
 
-  eprintln!(
-    "Score valuation: {} - Sun rate valuation: {}",
-    score_valuation, sunrate_valuation
-  );
-
-  return score_valuation * sunrate_valuation;
+  return breakdown;
 }
 
 pub fn simulate_action(game_state: &GameState, action: Action) -> GameState {
@@ -123,15 +113,26 @@ pub fn simulate_action(game_state: &GameState, action: Action) -> GameState {
   };
 }
 
-pub fn get_next_action(game_state: GameState, possible_actions: Vec<Action>) -> Action {
+pub fn get_next_action(
+  game_state: GameState,
+  possible_actions: Vec<Action>,
+  stats: &mut SearchStats,
+) -> Action {
   let mut chosen_action = Action::Wait;
-  let mut current_score = evaluate_state(&game_state);
+  let mut current_score = evaluate_state(&game_state).total;
+  stats.states_evaluated += 1;
+  stats.record_depth(1);
   let number_of_seeds = game_state
     .my_trees
     .iter()
     .filter(|tree| tree.size == 0)
     .count();
-  eprintln!("Evaluated score for action WAIT: {}", current_score);
+  log!(
+    Category::Eval,
+    Level::Debug,
+    "Evaluated score for action WAIT: {}",
+    current_score
+  );
 
   for possible_action in possible_actions {
     match possible_action {
@@ -146,17 +147,29 @@ pub fn get_next_action(game_state: GameState, possible_actions: Vec<Action>) ->
         {
           continue;
         }
-        eprintln!(
+        log!(
+          Category::Search,
+          Level::Debug,
           "Evaluating score for action {}....",
           action_to_order(possible_action)
         );
+        let generation_start = Instant::now();
         let new_state_with_action = simulate_action(&game_state, possible_action);
-        let new_state_score = evaluate_state(&new_state_with_action);
-
-        eprintln!(
+        stats.generation_time += generation_start.elapsed();
+        stats.nodes_expanded += 1;
+
+        let evaluation_start = Instant::now();
+        let breakdown = evaluate_state(&new_state_with_action);
+        stats.evaluation_time += evaluation_start.elapsed();
+        stats.states_evaluated += 1;
+        let new_state_score = breakdown.total;
+
+        log!(
+          Category::Eval,
+          Level::Debug,
           "Evaluated score for action {}: {}",
           action_to_order(possible_action),
-          new_state_score
+          breakdown
         );
 
         if new_state_score > current_score {
@@ -1,6 +1,11 @@
 //use crate::easing_functions::*;
+use crate::evaluation::EvalBreakdown;
 use crate::game_logic::*;
+use crate::log;
+use crate::logging::{Category, Level};
+use crate::search_stats::SearchStats;
 use std::collections::HashSet;
+use std::time::Instant;
 
 pub fn get_sunpoint_rate(game_state: &GameState) -> i32 {
   let shadows = get_shadows_in_field(
@@ -33,16 +38,19 @@ pub fn get_richness_score(game_state: &GameState) -> i32 {
     .fold(0, |a, b| a + b);
 }
 
-pub fn evaluate_state(game_state: &GameState) -> f32 {
+pub fn evaluate_state(game_state: &GameState) -> EvalBreakdown {
   let sunpoint_rate = get_sunpoint_rate(game_state) as f32;
 
   let game_completion_factor = (game_state.day as f32 / 23.0).powf(5.0);
-  let score_valuation = (1.0 + game_state.score as f32).powf(game_completion_factor);
-  let sunrate_valuation = (1.0 + sunpoint_rate).powf((1 as f32) - game_completion_factor);
   let richness_score = get_richness_score(game_state);
 
-  let state_value = score_valuation * sunrate_valuation + richness_score as f32 / 10000.0;
-  return state_value;
+  let mut breakdown = EvalBreakdown::new();
+  breakdown.score_term = (1.0 + game_state.score as f32).powf(game_completion_factor);
+  breakdown.sunrate_term = (1.0 + sunpoint_rate).powf((1 as f32) - game_completion_factor);
+  breakdown.richness_term = richness_score as f32 / 10000.0;
+  breakdown.total = breakdown.score_term * breakdown.sunrate_term + breakdown.richness_term;
+
+  return breakdown;
 }
 
 pub fn simulate_action(game_state: &GameState, action: Action) -> GameState {
@@ -101,17 +109,32 @@ pub fn simulate_action(game_state: &GameState, action: Action) -> GameState {
   };
 }
 
-pub fn get_next_action(game_state: GameState, seedable_cells: &Vec<Vec<Vec<i32>>>) -> Action {
+pub fn get_next_action(
+  game_state: GameState,
+  seedable_cells: &Vec<Vec<Vec<i32>>>,
+  stats: &mut SearchStats,
+) -> Action {
   let mut chosen_action = Action::Wait;
-  let mut current_score = evaluate_state(&game_state);
+  let mut current_score = evaluate_state(&game_state).total;
+  stats.states_evaluated += 1;
+  stats.record_depth(1);
   let number_of_seeds = game_state
     .my_trees
     .iter()
     .filter(|tree| tree.size == 0)
     .count();
-  eprintln!("Evaluated score for action WAIT: {}", current_score);
+  log!(
+    Category::Eval,
+    Level::Debug,
+    "Evaluated score for action WAIT: {}",
+    current_score
+  );
 
-  for possible_action in get_possible_actions(&game_state, seedable_cells) {
+  let generation_start = Instant::now();
+  let possible_actions = get_possible_actions(&game_state, seedable_cells);
+  stats.generation_time += generation_start.elapsed();
+
+  for possible_action in possible_actions {
     match possible_action {
       Action::Wait => {
         continue;
@@ -124,17 +147,29 @@ pub fn get_next_action(game_state: GameState, seedable_cells: &Vec<Vec<Vec<i32>>
         {
           continue;
         }
-        eprintln!(
+        log!(
+          Category::Search,
+          Level::Debug,
           "Evaluating score for action {}....",
           action_to_order(possible_action)
         );
+        let generation_start = Instant::now();
         let new_state_with_action = simulate_action(&game_state, possible_action);
-        let new_state_score = evaluate_state(&new_state_with_action);
-
-        eprintln!(
+        stats.generation_time += generation_start.elapsed();
+        stats.nodes_expanded += 1;
+
+        let evaluation_start = Instant::now();
+        let breakdown = evaluate_state(&new_state_with_action);
+        stats.evaluation_time += evaluation_start.elapsed();
+        stats.states_evaluated += 1;
+        let new_state_score = breakdown.total;
+
+        log!(
+          Category::Eval,
+          Level::Debug,
           "Evaluated score for action {}: {}",
           action_to_order(possible_action),
-          new_state_score
+          breakdown
         );
 
         if new_state_score > current_score {
@@ -0,0 +1,331 @@
+use crate::ai_bfs;
+use crate::evaluation::EvalBreakdown;
+use crate::game_logic::*;
+use crate::log;
+use crate::logging::{Category, Level};
+use crate::referee::*;
+use crate::rng::Random;
+use crate::rules::RuleSet;
+use crate::search_stats::SearchStats;
+use std::time::{Duration, Instant};
+
+pub const HORIZON_DAYS: usize = 4;
+pub const MAX_ACTIONS_PER_DAY: usize = 4;
+pub const POPULATION_SIZE: usize = 16;
+const ELITE_SIZE: usize = 2;
+const MUTATION_RATE: f64 = 0.2;
+const PLANNER_SEED: u64 = 2021;
+
+// Time left for the rest of the turn once the evolution stops, it would use all of it otherwise
+const TIME_MARGIN: Duration = Duration::from_millis(15);
+
+// Without a clock (the offline tools) the evolution stops after this many generations,
+// so the same game is played every time
+pub const MAX_GENERATIONS_WITHOUT_TIME_LIMIT: usize = 40;
+
+// The actions to play on each of the next HORIZON_DAYS days, every day ending with a WAIT
+#[derive(Clone)]
+struct Genome {
+  days: Vec<Vec<Action>>,
+  fitness: f32,
+}
+
+// Evolves plans for the next days and plays the first action of the best one. The population
+// is kept from one turn to the next, shifted by what happened since.
+pub struct RollingHorizonPlanner {
+  population: Vec<Genome>,
+  seed: u64,
+  random: Random,
+  // the day and action of the last turn we planned for
+  last_turn: Option<(i32, Action)>,
+}
+
+impl RollingHorizonPlanner {
+  pub fn new(seed: u64) -> RollingHorizonPlanner {
+    RollingHorizonPlanner {
+      population: Vec::new(),
+      seed: seed,
+      random: Random::new(seed),
+      last_turn: None,
+    }
+  }
+
+  pub fn get_next_action(
+    &mut self,
+    game_state: GameState,
+    seedable_cells: &Vec<Vec<Vec<i32>>>,
+    evaluate_state: fn(&GameState) -> EvalBreakdown,
+    stats: &mut SearchStats,
+  ) -> Action {
+    let start = Match {
+      game_state: game_state,
+      first_player_is_waiting: false,
+      rules: RuleSet::contest(),
+    };
+    let mut evaluator = Evaluator {
+      start: &start,
+      seedable_cells: seedable_cells,
+      evaluate_state: evaluate_state,
+      stats: stats,
+    };
+
+    // a new game starts from the same seed, so the same game is always played the same way
+    if self.is_new_game(start.game_state.day) {
+      *self = RollingHorizonPlanner::new(self.seed);
+    }
+    self.carry_over_population(start.game_state.day);
+
+    // the plans carried over are replayed from the new position, dropping what no longer fits
+    let mut population: Vec<Genome> = Vec::with_capacity(POPULATION_SIZE);
+    for genome in self.population.iter() {
+      population.push(evaluator.play(genome, 0.0, &mut self.random));
+    }
+    if population.is_empty() {
+      population.push(evaluator.play(&get_waiting_genome(), 0.0, &mut self.random));
+    }
+    while population.len() < POPULATION_SIZE {
+      population.push(evaluator.play(&get_waiting_genome(), 1.0, &mut self.random));
+    }
+
+    let mut number_of_generations = 0;
+    loop {
+      population.sort_by(|a, b| b.fitness.partial_cmp(&a.fitness).unwrap());
+
+      if evaluator.stats.is_near_deadline(TIME_MARGIN) {
+        break;
+      }
+      if !evaluator.stats.has_time_limit()
+        && number_of_generations >= MAX_GENERATIONS_WITHOUT_TIME_LIMIT
+      {
+        break;
+      }
+
+      let mut next_population: Vec<Genome> = population[..ELITE_SIZE].to_vec();
+      while next_population.len() < POPULATION_SIZE {
+        let first_parent = select_parent(&population, &mut self.random);
+        let second_parent = select_parent(&population, &mut self.random);
+        let child = crossover(first_parent, second_parent, &mut self.random);
+        next_population.push(evaluator.play(&child, MUTATION_RATE, &mut self.random));
+      }
+
+      population = next_population;
+      number_of_generations += 1;
+    }
+
+    let best_genome = &population[0];
+    let action = *best_genome.days[0].first().unwrap_or(&Action::Wait);
+
+    log!(
+      Category::Search,
+      Level::Info,
+      "{} generations, best plan {:.4}: {}",
+      number_of_generations,
+      best_genome.fitness,
+      describe_genome(best_genome)
+    );
+
+    self.population = population;
+    self.last_turn = Some((start.game_state.day, action));
+
+    return action;
+  }
+
+  // an earlier day than the last one planned for is the start of another game
+  fn is_new_game(&self, day: i32) -> bool {
+    return match self.last_turn {
+      Some((last_day, _)) => day < last_day,
+      None => false,
+    };
+  }
+
+  // Moves every plan forward to the given day: the action played last turn is dropped from
+  // the plans that started with it, and the days gone by are replaced by empty days
+  fn carry_over_population(&mut self, day: i32) {
+    let (last_day, last_action) = match self.last_turn {
+      Some(last_turn) => last_turn,
+      None => return,
+    };
+
+    for genome in self.population.iter_mut() {
+      if day == last_day {
+        if genome.days[0].first() == Some(&last_action) {
+          genome.days[0].remove(0);
+        }
+        continue;
+      }
+
+      let days_gone_by = ((day - last_day) as usize).min(HORIZON_DAYS);
+      genome.days.drain(..days_gone_by);
+      genome.days.resize(HORIZON_DAYS, Vec::new());
+    }
+  }
+}
+
+struct Evaluator<'a> {
+  start: &'a Match,
+  seedable_cells: &'a Vec<Vec<Vec<i32>>>,
+  evaluate_state: fn(&GameState) -> EvalBreakdown,
+  stats: &'a mut SearchStats,
+}
+
+impl<'a> Evaluator<'a> {
+  // Plays the genome with the shared resolver, the opponent waiting, and scores where it
+  // ends. Genes that aren't legal anymore are dropped. With a mutation rate, genes are
+  // also replaced by, or followed by, random legal actions along the way: the genome
+  // returned is the one actually played.
+  fn play(&mut self, genome: &Genome, mutation_rate: f64, random: &mut Random) -> Genome {
+    let generation_start = Instant::now();
+    let mut current_match = self.start.clone();
+    let mut played_days: Vec<Vec<Action>> = Vec::with_capacity(HORIZON_DAYS);
+
+    for day in 0..HORIZON_DAYS {
+      let mut played_actions: Vec<Action> = Vec::new();
+      let mut is_waiting = false;
+      let planned_actions = genome.days.get(day).map(|actions| actions.as_slice());
+
+      for planned_action in planned_actions.unwrap_or(&[]).iter() {
+        if is_game_over(&current_match) || played_actions.len() >= MAX_ACTIONS_PER_DAY {
+          break;
+        }
+
+        let legal_actions = get_legal_actions_for_player(&current_match, true, self.seedable_cells);
+        let action = if random.next_bool(mutation_rate) {
+          self.get_random_action(&current_match, &legal_actions, random)
+        } else if legal_actions.contains(planned_action) {
+          *planned_action
+        } else {
+          continue;
+        };
+
+        // a WAIT cuts the day short
+        if action == Action::Wait {
+          is_waiting = true;
+          break;
+        }
+        resolve_turn(&mut current_match, action, Action::Wait);
+        played_actions.push(action);
+      }
+
+      while !is_waiting
+        && !is_game_over(&current_match)
+        && played_actions.len() < MAX_ACTIONS_PER_DAY
+        && random.next_bool(mutation_rate)
+      {
+        let legal_actions = get_legal_actions_for_player(&current_match, true, self.seedable_cells);
+        let action = self.get_random_action(&current_match, &legal_actions, random);
+        if action == Action::Wait {
+          break;
+        }
+        resolve_turn(&mut current_match, action, Action::Wait);
+        played_actions.push(action);
+      }
+
+      self.stats.nodes_expanded += played_actions.len() as u64 + 1;
+      played_days.push(played_actions);
+
+      // we WAIT, and so does the opponent if they hadn't yet, which starts the next day
+      if !is_game_over(&current_match) {
+        resolve_turn(&mut current_match, Action::Wait, Action::Wait);
+      }
+    }
+    self.stats.generation_time += generation_start.elapsed();
+    self.stats.record_depth(
+      played_days
+        .iter()
+        .map(|actions| actions.len() + 1)
+        .sum::<usize>() as i32,
+    );
+
+    let evaluation_start = Instant::now();
+    let fitness = (self.evaluate_state)(&current_match.game_state).total;
+    self.stats.states_evaluated += 1;
+    self.stats.evaluation_time += evaluation_start.elapsed();
+
+    return Genome {
+      days: played_days,
+      fitness: fitness,
+    };
+  }
+
+  // Drawn from the actions the bfs considers: every legal seed would drown the plans in
+  // seeds the evaluator is too fond of
+  fn get_random_action(
+    &self,
+    current_match: &Match,
+    legal_actions: &Vec<Action>,
+    random: &mut Random,
+  ) -> Action {
+    let candidate_actions: Vec<Action> =
+      ai_bfs::get_possible_actions(&current_match.game_state, self.seedable_cells)
+        .into_iter()
+        .filter(|action| legal_actions.contains(action))
+        .collect();
+
+    return *random.choose(&candidate_actions).unwrap_or(&Action::Wait);
+  }
+}
+
+fn get_waiting_genome() -> Genome {
+  Genome {
+    days: vec![Vec::new(); HORIZON_DAYS],
+    fitness: f32::MIN,
+  }
+}
+
+// The better of two random genomes
+fn select_parent<'a>(population: &'a Vec<Genome>, random: &mut Random) -> &'a Genome {
+  let first = random.choose(population).unwrap();
+  let second = random.choose(population).unwrap();
+
+  return if first.fitness >= second.fitness {
+    first
+  } else {
+    second
+  };
+}
+
+// Each day of the child comes from either parent, a day's actions only make sense together
+fn crossover(first_parent: &Genome, second_parent: &Genome, random: &mut Random) -> Genome {
+  let days = (0..HORIZON_DAYS)
+    .map(|day| {
+      if random.next_bool(0.5) {
+        first_parent.days[day].clone()
+      } else {
+        second_parent.days[day].clone()
+      }
+    })
+    .collect();
+
+  return Genome {
+    days: days,
+    fitness: f32::MIN,
+  };
+}
+
+fn describe_genome(genome: &Genome) -> String {
+  let days: Vec<String> = genome
+    .days
+    .iter()
+    .map(|actions| {
+      let mut orders: Vec<String> = actions
+        .iter()
+        .map(|action| action_to_order(*action))
+        .collect();
+      orders.push("WAIT".to_string());
+      orders.join(", ")
+    })
+    .collect();
+
+  return days.join(" | ");
+}
+
+// A player with a planner of its own, so two of them in the same game don't mix their plans
+pub fn new_player() -> Player {
+  let mut planner = RollingHorizonPlanner::new(PLANNER_SEED);
+
+  return Box::new(
+    move |game_state: GameState, seedable_cells: &Vec<Vec<Vec<i32>>>, stats: &mut SearchStats| {
+      planner.get_next_action(game_state, seedable_cells, ai_bfs::evaluate_state, stats)
+    },
+  );
+}
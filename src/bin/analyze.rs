@@ -0,0 +1,107 @@
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::evaluation::EvalBreakdown;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::input_parsing::*;
+use codingame_spring_challenge_2021::logging;
+use codingame_spring_challenge_2021::render;
+use codingame_spring_challenge_2021::search_stats::SearchStats;
+use codingame_spring_challenge_2021::strategies::*;
+use std::env;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use std::process;
+
+// Scores every legal action of a position with a strategy's own evaluator
+fn main() {
+  // only what was asked for through BOT_LOG, the strategies are chatty otherwise
+  logging::disable_all();
+  logging::init_from_env();
+
+  let args: Vec<String> = env::args().collect();
+  if args.len() != 3 {
+    eprintln!("Usage: analyze <strategy> <notation | turn input file>");
+    eprintln!("Strategies: {}", get_strategy_names().join(", "));
+    process::exit(2);
+  }
+
+  let strategy = match find_strategy(&args[1]) {
+    Some(strategy) => strategy,
+    None => {
+      eprintln!("Unknown strategy: {}", args[1]);
+      eprintln!("Strategies: {}", get_strategy_names().join(", "));
+      process::exit(2);
+    }
+  };
+
+  let (board, game_state) = read_position(&args[2]);
+
+  println!("Position: {}", game_state.to_notation());
+  println!("Strategy: {}", strategy.name);
+  println!();
+  // the shadows that matter now are those of the next sun gathering
+  print!(
+    "{}",
+    render::ascii_with_shadows(&game_state, &board, game_state.day + 1)
+  );
+
+  match (strategy.simulate_action, strategy.evaluate_state) {
+    (Some(simulate_action), Some(evaluate_state)) => {
+      let mut scored_actions: Vec<(Action, GameState, EvalBreakdown)> =
+        get_my_legal_actions(&game_state, &board.seedable_cells)
+          .into_iter()
+          .map(|action| {
+            let new_game_state = simulate_action(&game_state, action);
+            let breakdown = evaluate_state(&new_game_state);
+            (action, new_game_state, breakdown)
+          })
+          .collect();
+
+      scored_actions.sort_by(|(_, _, score_a), (_, _, score_b)| {
+        score_b.total.partial_cmp(&score_a.total).unwrap()
+      });
+
+      for (action, new_game_state, breakdown) in &scored_actions {
+        println!();
+        println!(
+          "{:<12} {:>12.4}  -> {}",
+          action_to_order(*action),
+          breakdown.total,
+          new_game_state.to_notation()
+        );
+        println!("  {}", breakdown);
+      }
+    }
+    _ => println!("{} does not evaluate positions", strategy.name),
+  }
+
+  let mut stats = SearchStats::without_time_limit();
+  let mut player = (strategy.new_player)();
+  let chosen_action = player(game_state.clone(), &board.seedable_cells, &mut stats);
+  stats.finish();
+
+  println!();
+  println!("Chosen: {}", action_to_order(chosen_action));
+  println!("Search: {}", stats);
+}
+
+// A file holds the initial input followed by one turn input, anything else is notation
+// on the default board
+fn read_position(position: &str) -> (Board, GameState) {
+  if Path::new(position).is_file() {
+    let mut input = Cursor::new(fs::read_to_string(position).unwrap());
+    let board = Board::new(parse_initial_input(&mut input).cells);
+    let game_state = get_game_state(parse_turn_input(&mut input, &board.cells), &board.cells);
+
+    return (board, game_state);
+  }
+
+  let board = get_default_board();
+  match GameState::from_notation(position, &board) {
+    Ok(game_state) => (board, game_state),
+    Err(error) => {
+      eprintln!("Invalid position: {}", error);
+      process::exit(2);
+    }
+  }
+}
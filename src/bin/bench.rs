@@ -0,0 +1,261 @@
+use codingame_spring_challenge_2021::ai_bfs;
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::logging;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::hint::black_box;
+use std::process;
+use std::time::{Duration, Instant};
+
+// Positions the hot paths are timed on, from a sparse opening to a crowded endgame
+const POSITIONS: [(&str, &str); 3] = [
+  ("opening", "2 20 6:0 5:0:- 25A1,34A2,11A0,28B1,19B2,13B0"),
+  (
+    "midgame",
+    "11 18 14:12 12:10:- 0A3,7A2,1A2,8A1,25A3,4A0,20B3,30b2,22B1,13B2,16B1,31B0",
+  ),
+  (
+    "endgame",
+    "20 9 22:40 18:36:- 0A3,2A3,7A3,10A2,25A1,14A3,5A0,20B3,30B3,22B2,13B3,16B1,31B2,17B0",
+  ),
+];
+
+const WARM_UP_TIME: Duration = Duration::from_millis(200);
+const MIN_BATCH_TIME: Duration = Duration::from_millis(20);
+const NUMBER_OF_REPETITIONS: usize = 10;
+const DEFAULT_REGRESSION_THRESHOLD: f64 = 0.1;
+
+struct Benchmark<'a> {
+  name: String,
+  // runs the code once, returning how many nodes (actions, states, plans) it went through
+  run: Box<dyn FnMut() -> usize + 'a>,
+}
+
+struct Measurement {
+  name: String,
+  nanoseconds_per_op: f64,
+  nodes_per_op: usize,
+}
+
+// Times the engine hot paths, e.g. cargo run --release --bin bench -- --save bench.txt
+// before a change and --baseline bench.txt after it
+fn main() {
+  logging::disable_all();
+
+  let args: Vec<String> = env::args().skip(1).collect();
+  let mut save_path: Option<String> = None;
+  let mut baseline_path: Option<String> = None;
+  let mut regression_threshold = DEFAULT_REGRESSION_THRESHOLD;
+  let mut filter: Option<String> = None;
+
+  let mut i = 0;
+  while i < args.len() {
+    match (args[i].as_str(), args.get(i + 1)) {
+      ("--save", Some(path)) => save_path = Some(path.clone()),
+      ("--baseline", Some(path)) => baseline_path = Some(path.clone()),
+      ("--threshold", Some(threshold)) => {
+        regression_threshold = threshold.parse().unwrap_or_else(|_| usage())
+      }
+      ("--filter", Some(pattern)) => filter = Some(pattern.clone()),
+      _ => usage(),
+    }
+    i += 2;
+  }
+
+  let board = get_default_board();
+  let game_states: Vec<(&str, GameState)> = POSITIONS
+    .iter()
+    .map(|(name, notation)| (*name, GameState::from_notation(notation, &board).unwrap()))
+    .collect();
+
+  let mut benchmarks = get_benchmarks(&board, &game_states);
+  if let Some(pattern) = &filter {
+    benchmarks.retain(|benchmark| benchmark.name.contains(pattern.as_str()));
+  }
+
+  let baseline = baseline_path.as_ref().map(|path| read_baseline(path));
+  let mut measurements: Vec<Measurement> = Vec::new();
+  let mut number_of_regressions = 0;
+
+  println!(
+    "{:<45} {:>12} {:>14} {:>10}",
+    "benchmark", "ns/op", "nodes/sec", "baseline"
+  );
+
+  for mut benchmark in benchmarks {
+    let measurement = measure(&mut benchmark);
+
+    let comparison = match baseline
+      .as_ref()
+      .and_then(|baseline| baseline.get(&measurement.name))
+    {
+      Some(baseline_nanoseconds) => {
+        let change = measurement.nanoseconds_per_op / baseline_nanoseconds - 1.0;
+        if change > regression_threshold {
+          number_of_regressions += 1;
+          format!("{:>+9.1}% REGRESSION", change * 100.0)
+        } else {
+          format!("{:>+9.1}%", change * 100.0)
+        }
+      }
+      None => String::new(),
+    };
+
+    println!(
+      "{:<45} {:>12.0} {:>14.0} {}",
+      measurement.name,
+      measurement.nanoseconds_per_op,
+      measurement.nodes_per_op as f64 * 1e9 / measurement.nanoseconds_per_op,
+      comparison
+    );
+
+    measurements.push(measurement);
+  }
+
+  if let Some(path) = save_path {
+    let lines: Vec<String> = measurements
+      .iter()
+      .map(|measurement| format!("{} {:.1}", measurement.name, measurement.nanoseconds_per_op))
+      .collect();
+    fs::write(&path, lines.join("\n") + "\n").unwrap();
+    eprintln!("Baseline saved to {}", path);
+  }
+
+  if number_of_regressions > 0 {
+    eprintln!(
+      "{} benchmarks are more than {:.0}% slower than the baseline",
+      number_of_regressions,
+      regression_threshold * 100.0
+    );
+    process::exit(1);
+  }
+}
+
+fn get_benchmarks<'a>(
+  board: &'a Board,
+  game_states: &'a [(&str, GameState)],
+) -> Vec<Benchmark<'a>> {
+  let mut benchmarks: Vec<Benchmark<'a>> = Vec::new();
+
+  for (position_name, game_state) in game_states.iter() {
+    let actions = ai_bfs::get_possible_actions(game_state, &board.seedable_cells);
+
+    benchmarks.push(Benchmark {
+      name: format!("get_shadows_in_field/{}", position_name),
+      run: Box::new(move || {
+        let shadows = get_shadows_in_field(
+          game_state
+            .my_trees
+            .iter()
+            .chain(game_state.opponent_trees.iter()),
+          black_box(game_state.day),
+          &game_state.cells,
+        );
+        black_box(shadows);
+        1
+      }),
+    });
+    benchmarks.push(Benchmark {
+      name: format!("get_possible_actions/{}", position_name),
+      run: Box::new(move || {
+        black_box(ai_bfs::get_possible_actions(
+          black_box(game_state),
+          &board.seedable_cells,
+        ))
+        .len()
+      }),
+    });
+    benchmarks.push(Benchmark {
+      name: format!("simulate_action/{}", position_name),
+      run: Box::new(move || {
+        for action in actions.iter() {
+          black_box(ai_bfs::simulate_action(black_box(game_state), *action));
+        }
+        actions.len()
+      }),
+    });
+    benchmarks.push(Benchmark {
+      name: format!("evaluate_state/{}", position_name),
+      run: Box::new(move || {
+        black_box(ai_bfs::evaluate_state(black_box(game_state)));
+        1
+      }),
+    });
+    benchmarks.push(Benchmark {
+      name: format!("get_possible_actions_for_day/{}", position_name),
+      run: Box::new(move || {
+        black_box(ai_bfs::get_possible_actions_for_day(
+          black_box(game_state),
+          &board.seedable_cells,
+        ))
+        .len()
+      }),
+    });
+  }
+
+  return benchmarks;
+}
+
+// Warms up, sizes a batch to last at least MIN_BATCH_TIME, then keeps the median of the
+// repeated batches so one descheduling doesn't skew the result
+fn measure(benchmark: &mut Benchmark) -> Measurement {
+  let warm_up_start = Instant::now();
+  let mut nodes_per_op = 0;
+  while warm_up_start.elapsed() < WARM_UP_TIME {
+    nodes_per_op = (benchmark.run)();
+  }
+
+  let mut batch_size: u32 = 1;
+  loop {
+    let start = Instant::now();
+    for _ in 0..batch_size {
+      (benchmark.run)();
+    }
+    if start.elapsed() >= MIN_BATCH_TIME {
+      break;
+    }
+    batch_size *= 2;
+  }
+
+  let mut nanoseconds_per_op: Vec<f64> = (0..NUMBER_OF_REPETITIONS)
+    .map(|_| {
+      let start = Instant::now();
+      for _ in 0..batch_size {
+        (benchmark.run)();
+      }
+      start.elapsed().as_nanos() as f64 / batch_size as f64
+    })
+    .collect();
+  nanoseconds_per_op.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+  return Measurement {
+    name: benchmark.name.clone(),
+    nanoseconds_per_op: nanoseconds_per_op[NUMBER_OF_REPETITIONS / 2],
+    nodes_per_op,
+  };
+}
+
+// "<benchmark> <ns/op>" lines, as written by --save
+fn read_baseline(path: &str) -> HashMap<String, f64> {
+  return fs::read_to_string(path)
+    .unwrap_or_else(|error| {
+      eprintln!("Cannot read baseline {}: {}", path, error);
+      process::exit(2);
+    })
+    .lines()
+    .filter_map(|line| {
+      let mut parts = line.split_whitespace();
+      match (parts.next(), parts.next().map(|value| value.parse::<f64>())) {
+        (Some(name), Some(Ok(nanoseconds))) => Some((name.to_string(), nanoseconds)),
+        _ => None,
+      }
+    })
+    .collect();
+}
+
+fn usage() -> ! {
+  eprintln!("Usage: bench [--save FILE] [--baseline FILE] [--threshold FRACTION] [--filter TEXT]");
+  process::exit(2);
+}
@@ -0,0 +1,209 @@
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Inlines main.rs and every module it reaches into one file for the CodinGame submission.
+// Modules main.rs doesn't end up using, like the strategies it doesn't play, are left out.
+fn main() {
+  let args: Vec<String> = env::args().collect();
+  let source_directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+  let crate_name = env!("CARGO_PKG_NAME").replace('-', "_");
+
+  let bundle = bundle(&source_directory, &crate_name);
+
+  match args.get(1) {
+    Some(output_path) => {
+      fs::write(output_path, bundle).unwrap();
+      eprintln!("Bundle written to {}", output_path);
+    }
+    None => print!("{}", bundle),
+  }
+}
+
+fn bundle(source_directory: &Path, crate_name: &str) -> String {
+  let main_source = fs::read_to_string(source_directory.join("main.rs")).unwrap();
+  let lib_source = fs::read_to_string(source_directory.join("lib.rs")).unwrap();
+  let modules = get_modules(&lib_source);
+  let module_attributes = get_module_attributes(&lib_source);
+
+  let mut used_modules: HashSet<String> = HashSet::new();
+  let mut modules_to_visit = get_referenced_modules(
+    &main_source,
+    &format!("{}::", crate_name),
+    &modules,
+    source_directory,
+  );
+
+  while let Some(module) = modules_to_visit.pop() {
+    if !used_modules.insert(module.clone()) {
+      continue;
+    }
+
+    let module_source = fs::read_to_string(get_module_path(source_directory, &module)).unwrap();
+    modules_to_visit.extend(get_referenced_modules(
+      &module_source,
+      "crate::",
+      &modules,
+      source_directory,
+    ));
+  }
+
+  let mut bundle = String::new();
+  bundle.push_str("// Generated by the bundle binary, edit the files in src/ instead\n");
+  bundle.push_str(&get_inner_attributes(&lib_source));
+  bundle.push('\n');
+
+  // lib.rs order, so the bundle doesn't change between runs
+  for module in modules
+    .iter()
+    .filter(|module| used_modules.contains(*module))
+  {
+    let module_source = fs::read_to_string(get_module_path(source_directory, module)).unwrap();
+    bundle.push('\n');
+    bundle.push_str(&module_attributes[module]);
+    bundle.push_str(&format!("pub mod {} {{\n", module));
+    bundle.push_str(&module_source);
+    bundle.push_str("}\n");
+  }
+
+  bundle.push('\n');
+  bundle.push_str(&rewrite_main(&main_source, crate_name));
+
+  return bundle;
+}
+
+// main.rs now lives at the crate root next to the modules, so imports of the modules and
+// macros themselves would define them twice
+fn rewrite_main(main_source: &str, crate_name: &str) -> String {
+  let crate_prefix = format!("use {}::", crate_name);
+  let mut rewritten_main = String::new();
+
+  for line in main_source.lines() {
+    match line.strip_prefix(&crate_prefix) {
+      Some(path) if !path.contains("::") => continue,
+      Some(path) => {
+        let path = path.replace("{self, ", "{");
+        rewritten_main.push_str(&format!("use crate::{}", path));
+      }
+      None => rewritten_main.push_str(line),
+    }
+    rewritten_main.push('\n');
+  }
+
+  return rewritten_main;
+}
+
+fn get_module_path(source_directory: &Path, module: &str) -> PathBuf {
+  return source_directory.join(format!("{}.rs", module));
+}
+
+// the `pub mod` declarations of lib.rs, in order
+fn get_modules(lib_source: &str) -> Vec<String> {
+  return lib_source
+    .lines()
+    .filter_map(|line| line.trim().strip_prefix("pub mod "))
+    .map(|module| module.trim_end_matches(';').to_string())
+    .collect();
+}
+
+// the #![...] attributes at the top of lib.rs, which apply to the whole bundle too
+fn get_inner_attributes(lib_source: &str) -> String {
+  let mut attributes = String::new();
+  let mut is_inside_attribute = false;
+
+  for line in lib_source.lines() {
+    if line.starts_with("#![") {
+      is_inside_attribute = true;
+    }
+    if is_inside_attribute {
+      attributes.push_str(line);
+      attributes.push('\n');
+      if line.ends_with(']') {
+        is_inside_attribute = false;
+      }
+    }
+  }
+
+  return attributes;
+}
+
+// the #[...] attributes lib.rs puts on each `pub mod` declaration, like the lints allowed
+// in the contest modules
+fn get_module_attributes(lib_source: &str) -> HashMap<String, String> {
+  let mut module_attributes: HashMap<String, String> = HashMap::new();
+  let mut attributes = String::new();
+  let mut is_inside_attribute = false;
+
+  for line in lib_source.lines() {
+    if line.starts_with("#[") {
+      is_inside_attribute = true;
+    }
+    if is_inside_attribute {
+      attributes.push_str(line);
+      attributes.push('\n');
+      if line.ends_with(']') {
+        is_inside_attribute = false;
+      }
+      continue;
+    }
+
+    if let Some(module) = line.trim().strip_prefix("pub mod ") {
+      let module = module.trim_end_matches(';').to_string();
+      module_attributes.insert(module, attributes.clone());
+    }
+    attributes.clear();
+  }
+
+  return module_attributes;
+}
+
+// Modules named after `prefix`, either directly (crate::game_logic::...), in a group
+// (crate::{ai_bfs, game_logic}) or through a macro they export (crate::log)
+fn get_referenced_modules(
+  source: &str,
+  prefix: &str,
+  modules: &[String],
+  source_directory: &Path,
+) -> Vec<String> {
+  let mut referenced_names: Vec<String> = Vec::new();
+
+  for (position, _) in source.match_indices(prefix) {
+    let rest = &source[position + prefix.len()..];
+
+    if let Some(group) = rest.strip_prefix('{') {
+      let group_end = group.find('}').unwrap_or(group.len());
+      for name in group[..group_end].split(',') {
+        referenced_names.push(get_identifier(name.trim()));
+      }
+    } else {
+      referenced_names.push(get_identifier(rest));
+    }
+  }
+
+  let mut referenced_modules: Vec<String> = Vec::new();
+
+  for name in referenced_names {
+    if modules.contains(&name) {
+      referenced_modules.push(name);
+      continue;
+    }
+
+    let macro_definition = format!("macro_rules! {}", name);
+    for module in modules {
+      let module_source = fs::read_to_string(get_module_path(source_directory, module)).unwrap();
+      if module_source.contains(&macro_definition) {
+        referenced_modules.push(module.clone());
+      }
+    }
+  }
+
+  return referenced_modules;
+}
+
+fn get_identifier(text: &str) -> String {
+  return text
+    .chars()
+    .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+    .collect();
+}
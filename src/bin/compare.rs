@@ -0,0 +1,169 @@
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::logging;
+use codingame_spring_challenge_2021::referee::*;
+use codingame_spring_challenge_2021::statistics::*;
+use codingame_spring_challenge_2021::strategies::*;
+use std::env;
+use std::process;
+
+struct Options {
+  candidate: Strategy,
+  baseline: Strategy,
+  elo0: f64,
+  elo1: f64,
+  alpha: f64,
+  beta: f64,
+  max_games: i32,
+  seed: u64,
+}
+
+// Plays a candidate strategy against a baseline until a sequential probability ratio test
+// decides between H0 (the candidate is elo0 stronger) and H1 (it is elo1 stronger)
+fn main() {
+  logging::disable_all();
+  logging::init_from_env();
+
+  let options = parse_options();
+  let (lower_bound, upper_bound) = get_sprt_bounds(options.alpha, options.beta);
+  let (mut wins, mut draws, mut losses) = (0, 0, 0);
+  let mut log_likelihood_ratio = 0.0;
+
+  println!(
+    "{} vs {}, elo0 {} elo1 {}, alpha {} beta {}, LLR bounds [{:.2}, {:.2}]",
+    options.candidate.name,
+    options.baseline.name,
+    options.elo0,
+    options.elo1,
+    options.alpha,
+    options.beta,
+    lower_bound,
+    upper_bound
+  );
+
+  let mut board_index = 0;
+  while wins + draws + losses < options.max_games {
+    let setup = generate_game_setup(options.seed + board_index);
+    board_index += 1;
+
+    // both sides of the same board, so the board itself doesn't favour anyone
+    for candidate_is_first in [true, false].iter() {
+      let candidate_result = if *candidate_is_first {
+        play_game(
+          &setup.board,
+          &setup.first_player_trees,
+          &setup.second_player_trees,
+          (options.candidate.new_player)(),
+          (options.baseline.new_player)(),
+        )
+        .get_first_player_result()
+      } else {
+        1.0
+          - play_game(
+            &setup.board,
+            &setup.first_player_trees,
+            &setup.second_player_trees,
+            (options.baseline.new_player)(),
+            (options.candidate.new_player)(),
+          )
+          .get_first_player_result()
+      };
+
+      if candidate_result > 0.5 {
+        wins += 1;
+      } else if candidate_result < 0.5 {
+        losses += 1;
+      } else {
+        draws += 1;
+      }
+    }
+
+    let number_of_games = wins + draws + losses;
+    let score_fraction = (wins as f64 + draws as f64 / 2.0) / number_of_games as f64;
+    log_likelihood_ratio =
+      get_log_likelihood_ratio(wins, draws, losses, options.elo0, options.elo1);
+
+    println!(
+      "games {:>5}: {}/{}/{}, elo {:>+6.0}, LLR {:>+6.2}",
+      number_of_games,
+      wins,
+      draws,
+      losses,
+      get_elo_difference(score_fraction),
+      log_likelihood_ratio
+    );
+
+    if log_likelihood_ratio <= lower_bound || log_likelihood_ratio >= upper_bound {
+      break;
+    }
+  }
+
+  if log_likelihood_ratio >= upper_bound {
+    println!("H1 accepted: {} is better", options.candidate.name);
+  } else if log_likelihood_ratio <= lower_bound {
+    println!("H0 accepted: {} is not better", options.candidate.name);
+  } else {
+    println!("Undecided after {} games", wins + draws + losses);
+  }
+}
+
+fn parse_options() -> Options {
+  let args: Vec<String> = env::args().skip(1).collect();
+  let mut strategies: Vec<Strategy> = Vec::new();
+  let mut elo0 = 0.0;
+  let mut elo1 = 5.0;
+  let mut alpha = 0.05;
+  let mut beta = 0.05;
+  let mut max_games = 2000;
+  let mut seed = 1;
+
+  let mut i = 0;
+  while i < args.len() {
+    match (args[i].as_str(), args.get(i + 1)) {
+      ("--elo0", Some(value)) => elo0 = parse_value(value),
+      ("--elo1", Some(value)) => elo1 = parse_value(value),
+      ("--alpha", Some(value)) => alpha = parse_value(value),
+      ("--beta", Some(value)) => beta = parse_value(value),
+      ("--max-games", Some(value)) => max_games = parse_value(value),
+      ("--seed", Some(value)) => seed = parse_value(value),
+      (name, _) => {
+        match find_strategy(name) {
+          Some(strategy) => strategies.push(strategy),
+          None => {
+            eprintln!("Unknown strategy: {}", name);
+            usage();
+          }
+        }
+        i += 1;
+        continue;
+      }
+    }
+    i += 2;
+  }
+
+  if strategies.len() != 2 {
+    usage();
+  }
+
+  return Options {
+    candidate: strategies.remove(0),
+    baseline: strategies.remove(0),
+    elo0,
+    elo1,
+    alpha,
+    beta,
+    max_games,
+    seed,
+  };
+}
+
+fn parse_value<T: std::str::FromStr>(value: &str) -> T {
+  return value.parse().unwrap_or_else(|_| usage());
+}
+
+fn usage() -> ! {
+  eprintln!(
+    "Usage: compare <candidate> <baseline> [--elo0 E] [--elo1 E] [--alpha A] [--beta B] [--max-games N] [--seed S]"
+  );
+  eprintln!("Strategies: {}", get_strategy_names().join(", "));
+  process::exit(2);
+}
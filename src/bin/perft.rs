@@ -0,0 +1,62 @@
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::perft::*;
+use codingame_spring_challenge_2021::referee::*;
+use std::env;
+use std::process;
+use std::time::Instant;
+
+// Counts the joint action sequences reachable from a position, from the usual starting
+// position when none is given
+fn main() {
+  let args: Vec<String> = env::args().skip(1).collect();
+  let divide = args.iter().any(|arg| arg == "--divide");
+  let positional_args: Vec<&String> = args.iter().filter(|arg| *arg != "--divide").collect();
+
+  let depth: i32 = match positional_args.first().map(|depth| depth.parse()) {
+    Some(Ok(depth)) => depth,
+    _ => usage(),
+  };
+
+  let board = get_default_board();
+  let current_match = match positional_args.get(1) {
+    Some(notation) => match GameState::from_notation(notation, &board) {
+      Ok(game_state) => Match::from_game_state(game_state, &board),
+      Err(error) => {
+        eprintln!("Invalid position: {}", error);
+        process::exit(2);
+      }
+    },
+    None => new_match(&board, &vec![25, 34], &vec![28, 19]),
+  };
+
+  println!("Position: {}", current_match.game_state.to_notation());
+
+  let start = Instant::now();
+  let number_of_sequences = if divide {
+    let counts = perft_divide(&current_match, &board, depth);
+    for ((first_action, second_action), count) in counts.iter() {
+      println!(
+        "{:<12} {:<12} {}",
+        action_to_order(*first_action),
+        action_to_order(*second_action),
+        count
+      );
+    }
+    counts.iter().map(|(_, count)| count).sum()
+  } else {
+    perft(&current_match, &board, depth)
+  };
+
+  println!(
+    "perft({}) = {} in {} ms",
+    depth,
+    number_of_sequences,
+    start.elapsed().as_millis()
+  );
+}
+
+fn usage() -> ! {
+  eprintln!("Usage: perft <depth> [notation] [--divide]");
+  process::exit(2);
+}
@@ -0,0 +1,257 @@
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::logging;
+use codingame_spring_challenge_2021::referee::*;
+use codingame_spring_challenge_2021::render;
+use codingame_spring_challenge_2021::search_stats::*;
+use codingame_spring_challenge_2021::strategies::*;
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::process;
+
+const DEFAULT_STRATEGY: &str = "greedy_with_simpler_heuristic";
+
+struct Options {
+  strategy: Strategy,
+  seed: u64,
+  human_is_first_player: bool,
+}
+
+// A game against a strategy from the terminal. You are A on the board and type orders the
+// way the bot prints them: GROW 5, SEED 1 7, COMPLETE 3 or WAIT.
+fn main() {
+  logging::disable_all();
+  logging::init_from_env();
+
+  let options = parse_options();
+  let setup = generate_game_setup(options.seed);
+  let board = &setup.board;
+  let mut current_match = new_match(board, &setup.first_player_trees, &setup.second_player_trees);
+  let mut number_of_illegal_actions = [0; 2];
+  let mut bot = (options.strategy.new_player)();
+  let mut turn = 0;
+
+  let stdin = io::stdin();
+  let mut lines = stdin.lock().lines();
+
+  println!(
+    "You play {} against {} on board {}, \"?\" lists your legal orders",
+    if options.human_is_first_player {
+      "first"
+    } else {
+      "second"
+    },
+    options.strategy.name,
+    options.seed
+  );
+
+  while !is_game_over(&current_match) {
+    turn += 1;
+    let human_state = get_player_state(&current_match, options.human_is_first_player);
+    let human_legal_actions = get_legal_actions_for_player(
+      &current_match,
+      options.human_is_first_player,
+      &board.seedable_cells,
+    );
+
+    let human_action = if human_legal_actions.len() == 1 {
+      // already waiting, the bot plays on alone until the day ends
+      Action::Wait
+    } else {
+      print_position(&human_state, board);
+
+      loop {
+        print!("> ");
+        io::stdout().flush().unwrap();
+
+        let line = match lines.next() {
+          Some(Ok(line)) => line,
+          _ => {
+            println!();
+            return;
+          }
+        };
+
+        if line.trim() == "?" {
+          let orders: Vec<String> = human_legal_actions
+            .iter()
+            .map(|action| action_to_order(*action))
+            .collect();
+          println!("{}", orders.join(", "));
+          continue;
+        }
+
+        let action = match order_to_action(&line) {
+          Ok(action) => action,
+          Err(error) => {
+            println!("{}", error);
+            continue;
+          }
+        };
+
+        match get_illegal_action_reason(
+          &current_match,
+          options.human_is_first_player,
+          &board.seedable_cells,
+          action,
+        ) {
+          Some(reason) => println!("Illegal: {}", reason),
+          None if human_legal_actions.contains(&action) => break action,
+          None => println!("Illegal: {} isn't a legal action", action_to_order(action)),
+        }
+      }
+    };
+
+    let bot_is_first_player = !options.human_is_first_player;
+    let bot_legal_actions =
+      get_legal_actions_for_player(&current_match, bot_is_first_player, &board.seedable_cells);
+    let bot_action = if bot_legal_actions.len() == 1 {
+      Action::Wait
+    } else {
+      // the same limits as in a real game
+      let mut stats = SearchStats::new(if turn == 1 {
+        FIRST_TURN_TIME_LIMIT
+      } else {
+        TURN_TIME_LIMIT
+      });
+      let action = bot(
+        get_player_state(&current_match, bot_is_first_player),
+        &board.seedable_cells,
+        &mut stats,
+      );
+
+      if bot_legal_actions.contains(&action) {
+        action
+      } else {
+        println!(
+          "{} played the illegal {}, it WAITs instead",
+          options.strategy.name,
+          action_to_order(action)
+        );
+        number_of_illegal_actions[if bot_is_first_player { 0 } else { 1 }] += 1;
+        Action::Wait
+      }
+    };
+
+    if human_action != Action::Wait || bot_action != Action::Wait {
+      println!(
+        "You: {}, {}: {}",
+        action_to_order(human_action),
+        options.strategy.name,
+        action_to_order(bot_action)
+      );
+    }
+
+    let day = current_match.game_state.day;
+    if options.human_is_first_player {
+      resolve_turn(&mut current_match, human_action, bot_action);
+    } else {
+      resolve_turn(&mut current_match, bot_action, human_action);
+    }
+    if current_match.game_state.day != day && !is_game_over(&current_match) {
+      println!("--- day {} ---", current_match.game_state.day);
+    }
+  }
+
+  print_result(
+    &get_game_result(&current_match, number_of_illegal_actions),
+    options.human_is_first_player,
+    options.strategy.name,
+  );
+}
+
+fn print_position(game_state: &GameState, board: &Board) {
+  println!();
+  // shadows of tomorrow, the ones the sun of the next day depends on
+  print!(
+    "{}",
+    render::ascii_with_shadows(game_state, board, game_state.day + 1)
+  );
+  println!(
+    "day {}, nutrients {} | you: sun {} score {} | opponent: sun {} score {}{}",
+    game_state.day,
+    game_state.nutrients,
+    game_state.sunpoints,
+    game_state.score,
+    game_state.opponent_sunpoints,
+    game_state.opponent_score,
+    if game_state.opponent_is_waiting {
+      " (waiting)"
+    } else {
+      ""
+    }
+  );
+}
+
+fn print_result(result: &GameResult, human_is_first_player: bool, strategy_name: &str) {
+  let (human_points, bot_points, human_result) = if human_is_first_player {
+    (
+      result.first_player_points,
+      result.second_player_points,
+      result.get_first_player_result(),
+    )
+  } else {
+    (
+      result.second_player_points,
+      result.first_player_points,
+      1.0 - result.get_first_player_result(),
+    )
+  };
+
+  println!();
+  println!(
+    "Game over: you {} points, {} {} points",
+    human_points, strategy_name, bot_points
+  );
+  println!(
+    "{}",
+    if human_result > 0.5 {
+      "You win"
+    } else if human_result < 0.5 {
+      "You lose"
+    } else {
+      "Draw"
+    }
+  );
+}
+
+fn parse_options() -> Options {
+  let args: Vec<String> = env::args().skip(1).collect();
+  let mut strategy_name = DEFAULT_STRATEGY.to_string();
+  let mut seed = 1;
+  let mut human_is_first_player = true;
+
+  let mut i = 0;
+  while i < args.len() {
+    match args[i].as_str() {
+      "--seed" if i + 1 < args.len() => {
+        seed = args[i + 1].parse().unwrap_or_else(|_| usage());
+        i += 1;
+      }
+      "--second" => human_is_first_player = false,
+      name if !name.starts_with("--") => strategy_name = name.to_string(),
+      _ => usage(),
+    }
+    i += 1;
+  }
+
+  let strategy = match find_strategy(&strategy_name) {
+    Some(strategy) => strategy,
+    None => {
+      eprintln!("Unknown strategy: {}", strategy_name);
+      usage();
+    }
+  };
+
+  return Options {
+    strategy,
+    seed,
+    human_is_first_player,
+  };
+}
+
+fn usage() -> ! {
+  eprintln!("Usage: play [strategy] [--seed S] [--second]");
+  eprintln!("Strategies: {}", get_strategy_names().join(", "));
+  process::exit(2);
+}
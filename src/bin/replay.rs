@@ -0,0 +1,90 @@
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::input_parsing::*;
+use codingame_spring_challenge_2021::logging;
+use codingame_spring_challenge_2021::render;
+use codingame_spring_challenge_2021::search_stats::*;
+use codingame_spring_challenge_2021::strategies::*;
+use codingame_spring_challenge_2021::transcript::*;
+use std::env;
+use std::fs;
+use std::io::Cursor;
+use std::process;
+
+// Feeds a recorded game to a strategy and lists the turns where its decision changed
+fn main() {
+  // only what was asked for through BOT_LOG, the strategies are chatty otherwise
+  logging::disable_all();
+  logging::init_from_env();
+
+  let args: Vec<String> = env::args().collect();
+  let show_board = args.len() == 4 && args[3] == "--board";
+  if args.len() != 3 && !show_board {
+    eprintln!("Usage: replay <transcript file> <strategy> [--board]");
+    eprintln!("Strategies: {}", get_strategy_names().join(", "));
+    process::exit(2);
+  }
+
+  let strategy = match find_strategy(&args[2]) {
+    Some(strategy) => strategy,
+    None => {
+      eprintln!("Unknown strategy: {}", args[2]);
+      eprintln!("Strategies: {}", get_strategy_names().join(", "));
+      process::exit(2);
+    }
+  };
+
+  let transcript = parse_transcript(&fs::read_to_string(&args[1]).unwrap());
+  let initial_input = parse_initial_input(&mut Cursor::new(&transcript.initial_input));
+  let board = Board::new(initial_input.cells);
+
+  let mut number_of_changed_turns = 0;
+  let mut game_stats = GameStats::new();
+  let mut player = (strategy.new_player)();
+
+  for (i, recorded_turn) in transcript.turns.iter().enumerate() {
+    let turn_input = parse_turn_input(&mut Cursor::new(&recorded_turn.input), &board.cells);
+    let day = turn_input.day;
+    let game_state = get_game_state(turn_input, &board.cells);
+    let rendered_board = if show_board {
+      render::ascii(&game_state, &board)
+    } else {
+      String::new()
+    };
+
+    // timed against the same limits as in a real game
+    let mut stats = SearchStats::new(if i == 0 {
+      FIRST_TURN_TIME_LIMIT
+    } else {
+      TURN_TIME_LIMIT
+    });
+    let order = action_to_order(player(game_state, &board.seedable_cells, &mut stats));
+    stats.finish();
+    game_stats.record(i as i32 + 1, day, stats);
+
+    let recorded_order = match &recorded_turn.order {
+      Some(recorded_order) => recorded_order,
+      None => continue,
+    };
+
+    if order != *recorded_order {
+      number_of_changed_turns += 1;
+      println!(
+        "turn {} (day {}): played {}, now {}",
+        i + 1,
+        day,
+        recorded_order,
+        order
+      );
+      print!("{}", rendered_board);
+    }
+  }
+
+  println!(
+    "{} of {} turns changed with strategy {}",
+    number_of_changed_turns,
+    transcript.turns.len(),
+    strategy.name
+  );
+  println!("{}", game_stats.get_summary(SLOWEST_TURNS_IN_SUMMARY));
+}
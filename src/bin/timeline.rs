@@ -0,0 +1,111 @@
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::input_parsing::*;
+use codingame_spring_challenge_2021::render;
+use codingame_spring_challenge_2021::transcript::*;
+use std::env;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use std::process;
+
+// Renders every turn of a recorded game as an SVG frame, with an index.html page to step
+// through them. Everything is written to the output directory, nothing is loaded from outside.
+fn main() {
+  let args: Vec<String> = env::args().collect();
+  if args.len() != 3 {
+    eprintln!("Usage: timeline <transcript file> <output directory>");
+    process::exit(2);
+  }
+
+  let transcript = parse_transcript(&fs::read_to_string(&args[1]).unwrap());
+  let initial_input = parse_initial_input(&mut Cursor::new(&transcript.initial_input));
+  let board = Board::new(initial_input.cells);
+
+  let output_directory = Path::new(&args[2]);
+  fs::create_dir_all(output_directory).unwrap();
+
+  let mut frames: Vec<String> = Vec::new();
+
+  for (i, recorded_turn) in transcript.turns.iter().enumerate() {
+    let turn_input = parse_turn_input(&mut Cursor::new(&recorded_turn.input), &board.cells);
+    let game_state = get_game_state(turn_input, &board.cells);
+
+    let frame_file = format!("frame_{:03}.svg", i + 1);
+    fs::write(
+      output_directory.join(&frame_file),
+      render::svg(&game_state, &board),
+    )
+    .unwrap();
+
+    frames.push(get_frame_section(
+      i + 1,
+      transcript.turns.len(),
+      &frame_file,
+      &game_state.to_notation(),
+      recorded_turn.order.as_deref().unwrap_or("-"),
+    ));
+  }
+
+  fs::write(
+    output_directory.join("index.html"),
+    get_index_page(&args[1], &frames),
+  )
+  .unwrap();
+
+  eprintln!(
+    "{} frames written to {}",
+    frames.len(),
+    output_directory.display()
+  );
+}
+
+fn get_frame_section(
+  turn: usize,
+  number_of_turns: usize,
+  frame_file: &str,
+  notation: &str,
+  order: &str,
+) -> String {
+  let previous_link = if turn > 1 {
+    format!("<a href=\"#turn-{}\">&larr; prev</a>", turn - 1)
+  } else {
+    "<span>&larr; prev</span>".to_string()
+  };
+  let next_link = if turn < number_of_turns {
+    format!("<a href=\"#turn-{}\">next &rarr;</a>", turn + 1)
+  } else {
+    "<span>next &rarr;</span>".to_string()
+  };
+
+  return format!(
+    "<section id=\"turn-{turn}\">\n<h2>Turn {turn}</h2>\n<nav>{} | <a href=\"#top\">top</a> | {}</nav>\n<p><code>{}</code><br>played: <code>{}</code></p>\n<img src=\"{}\" alt=\"turn {turn}\">\n</section>\n",
+    previous_link,
+    next_link,
+    escape_html(notation),
+    escape_html(order),
+    frame_file,
+    turn = turn
+  );
+}
+
+fn get_index_page(title: &str, frames: &[String]) -> String {
+  let turn_links: Vec<String> = (1..=frames.len())
+    .map(|turn| format!("<a href=\"#turn-{}\">{}</a>", turn, turn))
+    .collect();
+
+  return format!(
+    "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\nbody {{ font-family: sans-serif; margin: 2em; }}\nsection {{ min-height: 100vh; }}\nnav a, nav span {{ margin: 0 0.3em; }}\nnav span {{ color: #999999; }}\n</style>\n</head>\n<body>\n<h1 id=\"top\">{}</h1>\n<nav>{}</nav>\n{}</body>\n</html>\n",
+    escape_html(title),
+    escape_html(title),
+    turn_links.join(" "),
+    frames.concat()
+  );
+}
+
+fn escape_html(text: &str) -> String {
+  return text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;");
+}
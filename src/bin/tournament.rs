@@ -0,0 +1,223 @@
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::logging;
+use codingame_spring_challenge_2021::referee::*;
+use codingame_spring_challenge_2021::rules::RuleSet;
+use codingame_spring_challenge_2021::statistics::*;
+use codingame_spring_challenge_2021::strategies::*;
+use std::env;
+use std::process;
+
+#[derive(Clone, Copy)]
+struct PairingStats {
+  wins: i32,
+  draws: i32,
+  losses: i32,
+  total_margin: i32,
+}
+
+impl PairingStats {
+  fn new() -> PairingStats {
+    return PairingStats {
+      wins: 0,
+      draws: 0,
+      losses: 0,
+      total_margin: 0,
+    };
+  }
+
+  fn get_number_of_games(&self) -> i32 {
+    return self.wins + self.draws + self.losses;
+  }
+
+  fn get_score_fraction(&self) -> f64 {
+    return (self.wins as f64 + self.draws as f64 / 2.0) / self.get_number_of_games() as f64;
+  }
+
+  fn add(&mut self, result: f32, margin: i32) {
+    if result > 0.5 {
+      self.wins += 1;
+    } else if result < 0.5 {
+      self.losses += 1;
+    } else {
+      self.draws += 1;
+    }
+    self.total_margin += margin;
+  }
+}
+
+struct Options {
+  number_of_boards: u64,
+  seed: u64,
+  rules: RuleSet,
+  strategies: Vec<Strategy>,
+}
+
+// Round robin between registered strategies, every board played twice with sides swapped.
+// --radius and --days play on other boards and game lengths than the contest's.
+fn main() {
+  logging::disable_all();
+  logging::init_from_env();
+
+  let options = parse_options();
+  let strategies = &options.strategies;
+  let mut stats = vec![vec![PairingStats::new(); strategies.len()]; strategies.len()];
+  let mut number_of_illegal_actions = vec![0; strategies.len()];
+
+  for board_index in 0..options.number_of_boards {
+    let setup = generate_game_setup_with_rules(options.seed + board_index, &options.rules);
+
+    for first in 0..strategies.len() {
+      for second in 0..strategies.len() {
+        if first == second {
+          continue;
+        }
+
+        let result = play_game(
+          &setup.board,
+          &setup.first_player_trees,
+          &setup.second_player_trees,
+          (strategies[first].new_player)(),
+          (strategies[second].new_player)(),
+        );
+        let first_player_result = result.get_first_player_result();
+        let margin = result.first_player_points - result.second_player_points;
+
+        stats[first][second].add(first_player_result, margin);
+        stats[second][first].add(1.0 - first_player_result, -margin);
+        number_of_illegal_actions[first] += result.number_of_illegal_actions[0];
+        number_of_illegal_actions[second] += result.number_of_illegal_actions[1];
+      }
+    }
+
+    eprintln!(
+      "board {}/{} done",
+      board_index + 1,
+      options.number_of_boards
+    );
+  }
+
+  print_crosstable(strategies, &stats, &number_of_illegal_actions);
+}
+
+fn parse_options() -> Options {
+  let args: Vec<String> = env::args().skip(1).collect();
+  let mut options = Options {
+    number_of_boards: 10,
+    seed: 1,
+    rules: RuleSet::contest(),
+    strategies: Vec::new(),
+  };
+  let mut number_of_days: Option<i32> = None;
+
+  let mut i = 0;
+  while i < args.len() {
+    match args[i].as_str() {
+      "--boards" if i + 1 < args.len() => {
+        options.number_of_boards = args[i + 1].parse().unwrap_or_else(|_| usage());
+        i += 1;
+      }
+      "--seed" if i + 1 < args.len() => {
+        options.seed = args[i + 1].parse().unwrap_or_else(|_| usage());
+        i += 1;
+      }
+      "--radius" if i + 1 < args.len() => {
+        options.rules = RuleSet::with_board_radius(args[i + 1].parse().unwrap_or_else(|_| usage()));
+        i += 1;
+      }
+      "--days" if i + 1 < args.len() => {
+        number_of_days = Some(args[i + 1].parse().unwrap_or_else(|_| usage()));
+        i += 1;
+      }
+      name => match find_strategy(name) {
+        Some(strategy) => options.strategies.push(strategy),
+        None => {
+          eprintln!("Unknown strategy: {}", name);
+          usage();
+        }
+      },
+    }
+    i += 1;
+  }
+
+  if let Some(number_of_days) = number_of_days {
+    options.rules.number_of_days = number_of_days;
+  }
+  if let Err(error) = options.rules.validate() {
+    eprintln!("Invalid rules: {}", error);
+    process::exit(2);
+  }
+
+  if options.strategies.is_empty() {
+    options.strategies = get_strategies();
+  }
+  if options.strategies.len() < 2 {
+    usage();
+  }
+
+  return options;
+}
+
+fn usage() -> ! {
+  eprintln!("Usage: tournament [--boards N] [--seed S] [--radius R] [--days D] [strategy...]");
+  eprintln!("Strategies: {}", get_strategy_names().join(", "));
+  process::exit(2);
+}
+
+fn print_crosstable(
+  strategies: &[Strategy],
+  stats: &[Vec<PairingStats>],
+  number_of_illegal_actions: &[i32],
+) {
+  let name_width = strategies
+    .iter()
+    .map(|strategy| strategy.name.len())
+    .max()
+    .unwrap();
+
+  print!("{:<width$}", "", width = name_width);
+  for column in 0..strategies.len() {
+    print!(" | {:^18}", column + 1);
+  }
+  println!(
+    " | {:>7} | {:>7} | {:>8} | illegal",
+    "score", "elo", "margin"
+  );
+
+  for (row, strategy) in strategies.iter().enumerate() {
+    let mut total = PairingStats::new();
+
+    print!("{:<width$}", strategy.name, width = name_width);
+    for (column, pairing) in stats[row].iter().enumerate() {
+      if row == column {
+        print!(" | {:^18}", "-");
+        continue;
+      }
+
+      print!(
+        " | {:>3}/{:>3}/{:>3} {:>+6.1}",
+        pairing.wins,
+        pairing.draws,
+        pairing.losses,
+        pairing.total_margin as f64 / pairing.get_number_of_games() as f64
+      );
+
+      total.wins += pairing.wins;
+      total.draws += pairing.draws;
+      total.losses += pairing.losses;
+      total.total_margin += pairing.total_margin;
+    }
+
+    let score_fraction = total.get_score_fraction();
+    println!(
+      " | {:>6.1}% | {:>+7.0} | {:>+8.2} | {}",
+      score_fraction * 100.0,
+      get_elo_difference(score_fraction),
+      total.total_margin as f64 / total.get_number_of_games() as f64,
+      number_of_illegal_actions[row]
+    );
+  }
+
+  println!();
+  println!("Cells are wins/draws/losses and average margin of the row against the column,");
+  println!("elo is relative to the rest of the field.");
+}
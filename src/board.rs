@@ -0,0 +1,193 @@
+use crate::game_logic::*;
+use crate::rng::Random;
+use crate::rules::*;
+
+pub struct Board {
+  pub cells: Vec<Cell>,
+  // cells a tree on the given cell can seed, indexed by [cell][size - 1]
+  pub seedable_cells: Vec<Vec<Vec<i32>>>,
+  pub rules: RuleSet,
+}
+
+impl Board {
+  pub fn new(cells: Vec<Cell>) -> Board {
+    return Board::with_rules(cells, RuleSet::contest());
+  }
+
+  pub fn with_rules(cells: Vec<Cell>, rules: RuleSet) -> Board {
+    let mut seedable_cells: Vec<Vec<Vec<i32>>> = Vec::with_capacity(cells.len());
+
+    for cell in 0..cells.len() {
+      seedable_cells.push(Vec::with_capacity(rules.seed_range_per_size.len()));
+
+      for seed_range in rules.seed_range_per_size.iter() {
+        seedable_cells[cell].push(get_seedable_cells_for_cell_and_size(
+          cell,
+          *seed_range,
+          &cells,
+        ));
+      }
+    }
+
+    Board {
+      cells: cells,
+      seedable_cells: seedable_cells,
+      rules: rules,
+    }
+  }
+}
+
+// cube coordinate offsets, in the same order as the neighbours of a cell
+pub const DIRECTIONS: [(i32, i32, i32); 6] = [
+  (1, -1, 0),
+  (1, 0, -1),
+  (0, 1, -1),
+  (-1, 1, 0),
+  (-1, 0, 1),
+  (0, -1, 1),
+];
+
+// Cube coordinates of every cell, indexed like the referee does: 0 is the center and
+// the next cells spiral outwards ring by ring
+pub fn get_cell_coordinates(radius: i32) -> Vec<(i32, i32, i32)> {
+  let mut coordinates = vec![(0, 0, 0)];
+
+  for ring in 1..=radius {
+    let mut coordinate = (
+      DIRECTIONS[0].0 * ring,
+      DIRECTIONS[0].1 * ring,
+      DIRECTIONS[0].2 * ring,
+    );
+
+    for side in 0..6 {
+      for _ in 0..ring {
+        coordinates.push(coordinate);
+        let direction = DIRECTIONS[(side + 2) % 6];
+        coordinate = (
+          coordinate.0 + direction.0,
+          coordinate.1 + direction.1,
+          coordinate.2 + direction.2,
+        );
+      }
+    }
+  }
+
+  return coordinates;
+}
+
+pub fn get_ring(coordinate: (i32, i32, i32)) -> i32 {
+  return coordinate
+    .0
+    .abs()
+    .max(coordinate.1.abs())
+    .max(coordinate.2.abs());
+}
+
+pub fn generate_cells(radius: i32) -> Vec<Cell> {
+  let coordinates = get_cell_coordinates(radius);
+
+  return coordinates
+    .iter()
+    .enumerate()
+    .map(|(index, coordinate)| {
+      let mut neighbours = [-1; 6];
+      for (direction, offset) in DIRECTIONS.iter().enumerate() {
+        let neighbour = (
+          coordinate.0 + offset.0,
+          coordinate.1 + offset.1,
+          coordinate.2 + offset.2,
+        );
+        if let Some(neighbour_index) = coordinates.iter().position(|other| *other == neighbour) {
+          neighbours[direction] = neighbour_index as i32;
+        }
+      }
+
+      let ring = get_ring(*coordinate);
+      Cell {
+        index: index as i32,
+        richness: if ring == radius {
+          1
+        } else if ring == radius - 1 {
+          2
+        } else {
+          3
+        },
+        neighbours: neighbours,
+      }
+    })
+    .collect();
+}
+
+// The contest board with every cell usable
+pub fn get_default_board() -> Board {
+  return Board::new(generate_cells(CONTEST_BOARD_RADIUS));
+}
+
+pub struct GameSetup {
+  pub board: Board,
+  pub first_player_trees: Vec<i32>,
+  pub second_player_trees: Vec<i32>,
+}
+
+pub fn get_opposite_cell(cell_index: i32, radius: i32) -> i32 {
+  let coordinates = get_cell_coordinates(radius);
+  let coordinate = coordinates[cell_index as usize];
+  let opposite = (-coordinate.0, -coordinate.1, -coordinate.2);
+
+  return coordinates
+    .iter()
+    .position(|other| *other == opposite)
+    .unwrap() as i32;
+}
+
+// Like the referee: unusable cells and starting trees are placed symmetrically, so both
+// players start from the same position. The starting trees sit on the outer ring.
+pub fn generate_game_setup(seed: u64) -> GameSetup {
+  return generate_game_setup_with_rules(seed, &RuleSet::contest());
+}
+
+pub fn generate_game_setup_with_rules(seed: u64, rules: &RuleSet) -> GameSetup {
+  let radius = rules.board_radius;
+  let mut random = Random::new(seed);
+  let mut cells = generate_cells(radius);
+
+  let number_of_unusable_cells = random.next_below(rules.max_unusable_cells / 2 + 1) * 2;
+  let mut unusable_cells = 0;
+  while unusable_cells < number_of_unusable_cells {
+    let cell_index = 1 + random.next_below(cells.len() - 1) as i32;
+    let opposite_cell = get_opposite_cell(cell_index, radius);
+
+    if cells[cell_index as usize].richness > 0 {
+      cells[cell_index as usize].richness = 0;
+      cells[opposite_cell as usize].richness = 0;
+      unusable_cells += 2;
+    }
+  }
+
+  let coordinates = get_cell_coordinates(radius);
+  let outer_ring: Vec<i32> = (0..cells.len() as i32)
+    .filter(|cell_index| get_ring(coordinates[*cell_index as usize]) == radius)
+    .collect();
+
+  let mut first_player_trees: Vec<i32> = Vec::new();
+  let mut second_player_trees: Vec<i32> = Vec::new();
+  while first_player_trees.len() < STARTING_TREES_PER_PLAYER {
+    let cell_index = outer_ring[random.next_below(outer_ring.len())];
+    let opposite_cell = get_opposite_cell(cell_index, radius);
+    let is_taken = first_player_trees
+      .iter()
+      .chain(second_player_trees.iter())
+      .any(|taken_cell| *taken_cell == cell_index || *taken_cell == opposite_cell);
+
+    if cells[cell_index as usize].richness > 0 && !is_taken {
+      first_player_trees.push(cell_index);
+      second_player_trees.push(opposite_cell);
+    }
+  }
+
+  return GameSetup {
+    board: Board::with_rules(cells, rules.clone()),
+    first_player_trees: first_player_trees,
+    second_player_trees: second_player_trees,
+  };
+}
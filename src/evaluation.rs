@@ -0,0 +1,47 @@
+use std::fmt;
+
+// Named terms an evaluator combined into its total, so they can be inspected without
+// the evaluator printing anything itself
+#[derive(Clone, Copy)]
+pub struct EvalBreakdown {
+  pub score_term: f32,
+  pub sunrate_term: f32,
+  pub enemy_term: f32,
+  pub richness_term: f32,
+  pub spare_sun_term: f32,
+  pub total: f32,
+}
+
+impl Default for EvalBreakdown {
+  fn default() -> EvalBreakdown {
+    return EvalBreakdown::new();
+  }
+}
+
+impl EvalBreakdown {
+  pub fn new() -> EvalBreakdown {
+    EvalBreakdown {
+      score_term: 0.0,
+      sunrate_term: 0.0,
+      enemy_term: 0.0,
+      richness_term: 0.0,
+      spare_sun_term: 0.0,
+      total: 0.0,
+    }
+  }
+}
+
+impl fmt::Display for EvalBreakdown {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "score: {:.4}, sun rate: {:.4}, enemy: {:.4}, richness: {:.4}, spare sun: {:.4}, total: {:.4}",
+      self.score_term,
+      self.sunrate_term,
+      self.enemy_term,
+      self.richness_term,
+      self.spare_sun_term,
+      self.total
+    )
+  }
+}
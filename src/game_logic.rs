@@ -1,3 +1,4 @@
+use crate::rules::*;
 use std::collections::HashMap;
 
 #[derive(Clone)]
@@ -20,7 +21,10 @@ pub struct Tree {
 
 impl Copy for Tree {}
 
-#[derive(Clone, Copy)]
+// the size of the tallest shadow on each cell, indexed by cell
+pub type Shadows = [i32; MAX_NUMBER_OF_CELLS];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Action {
   Wait,
   Grow(i32),
@@ -37,6 +41,8 @@ pub struct GameState {
   pub my_trees: Vec<Tree>,
   pub opponent_trees: Vec<Tree>,
   pub opponent_score: i32,
+  pub opponent_sunpoints: i32,
+  pub opponent_is_waiting: bool,
 }
 
 impl Clone for GameState {
@@ -50,11 +56,22 @@ impl Clone for GameState {
       my_trees: self.my_trees.iter().copied().collect(),
       opponent_trees: self.opponent_trees.iter().copied().collect(),
       opponent_score: self.opponent_score,
+      opponent_sunpoints: self.opponent_sunpoints,
+      opponent_is_waiting: self.opponent_is_waiting,
     }
   }
 }
 
 pub fn get_sun_cost_to_grow(new_size: i32, my_trees: &Vec<Tree>) -> i32 {
+  return get_sun_cost_to_grow_with_costs(new_size, my_trees, &CONTEST_GROW_COSTS);
+}
+
+// grow_costs as in RuleSet, indexed by the new size
+pub fn get_sun_cost_to_grow_with_costs(
+  new_size: i32,
+  my_trees: &Vec<Tree>,
+  grow_costs: &[i32],
+) -> i32 {
   let number_of_target_size_trees = my_trees
     .iter()
     .map(|tree| if tree.size == new_size { 1 } else { 0 })
@@ -62,37 +79,42 @@ pub fn get_sun_cost_to_grow(new_size: i32, my_trees: &Vec<Tree>) -> i32 {
     .unwrap();
 
   return number_of_target_size_trees
-    + match new_size {
-      0 => 0,
-      1 => 1,
-      2 => 3,
-      3 => 7,
+    + match grow_costs.get(new_size as usize) {
+      Some(cost) if new_size >= 0 => *cost,
       _ => panic!("Invalid tree size: {}", new_size),
     };
 }
 
-pub fn get_sun_cost_to_completion(target_tree: &Tree, my_trees: &Vec<Tree>) -> i32 {
+pub fn get_sun_cost_to_completion(
+  target_tree: &Tree,
+  my_trees: &Vec<Tree>,
+  rules: &RuleSet,
+) -> i32 {
   let mut current_size = target_tree.size;
   let mut current_cost = 0;
-  while current_size < 3 {
+  while current_size < rules.get_max_tree_size() {
     current_size += 1;
 
-    current_cost += get_sun_cost_to_grow(current_size, my_trees)
+    current_cost += get_sun_cost_to_grow_with_costs(current_size, my_trees, &rules.grow_costs)
   }
 
-  return current_cost + 4;
+  return current_cost + rules.completion_cost;
 }
 
-pub fn get_sun_cost_to_completion_from_size(from_size: i32, my_trees: &Vec<Tree>) -> i32 {
+pub fn get_sun_cost_to_completion_from_size(
+  from_size: i32,
+  my_trees: &Vec<Tree>,
+  rules: &RuleSet,
+) -> i32 {
   let mut current_size = from_size;
   let mut current_cost = 0;
-  while current_size < 3 {
+  while current_size < rules.get_max_tree_size() {
     current_size += 1;
 
-    current_cost += get_sun_cost_to_grow(current_size, my_trees)
+    current_cost += get_sun_cost_to_grow_with_costs(current_size, my_trees, &rules.grow_costs)
   }
 
-  return current_cost + 4;
+  return current_cost + rules.completion_cost;
 }
 
 pub fn get_score_for_cell(nutrients: i32, cell: &Cell) -> i32 {
@@ -108,12 +130,39 @@ pub fn action_to_order(action: Action) -> String {
   }
 }
 
+// Inverse of action_to_order, for orders typed by hand
+pub fn order_to_action(order: &str) -> Result<Action, String> {
+  let words: Vec<&str> = order.split_whitespace().collect();
+  let parse_cell = |word: &str| -> Result<i32, String> {
+    return word
+      .parse::<i32>()
+      .map_err(|_| format!("Invalid cell index: {}", word));
+  };
+
+  return match words.as_slice() {
+    [command] if command.eq_ignore_ascii_case("WAIT") => Ok(Action::Wait),
+    [command, target] if command.eq_ignore_ascii_case("GROW") => {
+      Ok(Action::Grow(parse_cell(target)?))
+    }
+    [command, source, target] if command.eq_ignore_ascii_case("SEED") => {
+      Ok(Action::Seed(parse_cell(source)?, parse_cell(target)?))
+    }
+    [command, target] if command.eq_ignore_ascii_case("COMPLETE") => {
+      Ok(Action::Complete(parse_cell(target)?))
+    }
+    _ => Err(format!(
+      "Invalid order: {} (expected WAIT, GROW <cell>, SEED <cell> <cell> or COMPLETE <cell>)",
+      order.trim()
+    )),
+  };
+}
+
 pub fn get_shadows_in_field<'a>(
   trees: impl Iterator<Item = &'a Tree>,
   day: i32,
   cells: &Vec<Cell>,
-) -> [i32; 37] {
-  let mut shadows = [0; 37];
+) -> Shadows {
+  let mut shadows = [0; MAX_NUMBER_OF_CELLS];
   let shadow_direction = (day % 6) as usize;
 
   for current_tree in trees {
@@ -131,13 +180,17 @@ pub fn get_maxed_out_shadows_in_field<'a>(
   trees: impl Iterator<Item = &'a Tree>,
   day: i32,
   cells: &Vec<Cell>,
-) -> [i32; 37] {
-  let mut shadows = [0; 37];
+  rules: &RuleSet,
+) -> Shadows {
+  let mut shadows = [0; MAX_NUMBER_OF_CELLS];
   let shadow_direction = (day % 6) as usize;
+  let max_tree_size = rules.get_max_tree_size();
 
   for current_tree in trees {
-    for cell in get_maxed_out_tree_shadow_cells(current_tree.cell_index, cells, shadow_direction) {
-      shadows[cell as usize] = 3
+    for cell in
+      get_maxed_out_tree_shadow_cells(current_tree.cell_index, cells, shadow_direction, rules)
+    {
+      shadows[cell as usize] = max_tree_size
     }
   }
 
@@ -148,18 +201,22 @@ pub fn get_maxed_out_shadows_in_field_with_simulated_trees<'a>(
   trees: impl Iterator<Item = &'a Tree>,
   day: i32,
   cells: &Vec<Cell>,
-) -> [i32; 37] {
-  let mut shadows = [0; 37];
+  rules: &RuleSet,
+) -> Shadows {
+  let mut shadows = [0; MAX_NUMBER_OF_CELLS];
   let shadow_direction = (day % 6) as usize;
+  let max_tree_size = rules.get_max_tree_size();
 
   for current_tree in trees {
-    for cell in get_maxed_out_tree_shadow_cells(current_tree.cell_index, cells, shadow_direction) {
-      shadows[cell as usize] = 3
+    for cell in
+      get_maxed_out_tree_shadow_cells(current_tree.cell_index, cells, shadow_direction, rules)
+    {
+      shadows[cell as usize] = max_tree_size
     }
   }
 
-  for i in 0..37 {
-    for cell in get_maxed_out_tree_shadow_cells(i, cells, shadow_direction) {
+  for i in 0..cells.len() as i32 {
+    for cell in get_maxed_out_tree_shadow_cells(i, cells, shadow_direction, rules) {
       if shadows[cell as usize] < 1 {
         shadows[cell as usize] = 1
       }
@@ -173,12 +230,15 @@ pub fn get_maxed_out_shadows_in_field_with_real_size<'a>(
   trees: impl Iterator<Item = &'a Tree>,
   day: i32,
   cells: &Vec<Cell>,
-) -> [i32; 37] {
-  let mut shadows = [0; 37];
+  rules: &RuleSet,
+) -> Shadows {
+  let mut shadows = [0; MAX_NUMBER_OF_CELLS];
   let shadow_direction = (day % 6) as usize;
 
   for current_tree in trees {
-    for cell in get_maxed_out_tree_shadow_cells(current_tree.cell_index, cells, shadow_direction) {
+    for cell in
+      get_maxed_out_tree_shadow_cells(current_tree.cell_index, cells, shadow_direction, rules)
+    {
       if shadows[cell as usize] < current_tree.size {
         shadows[cell as usize] = current_tree.size
       }
@@ -192,11 +252,12 @@ pub fn get_maxed_out_tree_shadow_cells(
   cell_index: i32,
   cells: &Vec<Cell>,
   direction: usize,
+  rules: &RuleSet,
 ) -> Vec<i32> {
   let mut cells_within_reach: Vec<i32> = Vec::new();
 
   let mut current_neighbour = cells[cell_index as usize].neighbours[direction];
-  for _ in 0..3 as usize {
+  for _ in 0..rules.get_max_tree_size() as usize {
     if current_neighbour == -1 {
       break;
     }
@@ -266,7 +327,11 @@ pub fn get_tree_seedable_cells(tree: &Tree, cells: &Vec<Cell>) -> Vec<i32> {
     }
   }
 
-  return lowest_distance_to_cells.iter().map(|(k, _)| *k).collect();
+  // sorted, a HashMap's order changes from one run to the next
+  let mut seedable_cells: Vec<i32> = lowest_distance_to_cells.iter().map(|(k, _)| *k).collect();
+  seedable_cells.sort();
+
+  return seedable_cells;
 }
 
 pub fn get_seedable_cells_for_cell_and_size(
@@ -314,5 +379,77 @@ pub fn get_seedable_cells_for_cell_and_size(
     }
   }
 
-  return lowest_distance_to_cells.iter().map(|(k, _)| *k).collect();
+  // sorted, a HashMap's order changes from one run to the next
+  let mut seedable_cells: Vec<i32> = lowest_distance_to_cells.iter().map(|(k, _)| *k).collect();
+  seedable_cells.sort();
+
+  return seedable_cells;
+}
+
+pub fn get_legal_actions(
+  trees: &Vec<Tree>,
+  other_trees: &Vec<Tree>,
+  sunpoints: i32,
+  cells: &Vec<Cell>,
+  seedable_cells: &Vec<Vec<Vec<i32>>>,
+) -> Vec<Action> {
+  return with_contest_rules(|rules| {
+    get_legal_actions_with_rules(trees, other_trees, sunpoints, cells, seedable_cells, rules)
+  });
+}
+
+pub fn get_legal_actions_with_rules(
+  trees: &Vec<Tree>,
+  other_trees: &Vec<Tree>,
+  sunpoints: i32,
+  cells: &Vec<Cell>,
+  seedable_cells: &Vec<Vec<Vec<i32>>>,
+  rules: &RuleSet,
+) -> Vec<Action> {
+  let max_tree_size = rules.get_max_tree_size();
+  let get_grow_cost =
+    |new_size: i32| get_sun_cost_to_grow_with_costs(new_size, trees, &rules.grow_costs);
+  let mut legal_actions = vec![Action::Wait];
+
+  for tree in trees {
+    if tree.is_dormant {
+      continue;
+    }
+
+    if tree.size == max_tree_size && sunpoints >= rules.completion_cost {
+      legal_actions.push(Action::Complete(tree.cell_index));
+    }
+
+    if tree.size < max_tree_size && get_grow_cost(tree.size + 1) <= sunpoints {
+      legal_actions.push(Action::Grow(tree.cell_index));
+    }
+
+    if tree.size > 0 && get_grow_cost(0) <= sunpoints {
+      for cell in &seedable_cells[tree.cell_index as usize][(tree.size - 1) as usize] {
+        let is_occupied = trees
+          .iter()
+          .chain(other_trees.iter())
+          .any(|other_tree| other_tree.cell_index == *cell);
+
+        if cells[*cell as usize].richness > 0 && !is_occupied {
+          legal_actions.push(Action::Seed(tree.cell_index, *cell));
+        }
+      }
+    }
+  }
+
+  return legal_actions;
+}
+
+pub fn get_my_legal_actions(
+  game_state: &GameState,
+  seedable_cells: &Vec<Vec<Vec<i32>>>,
+) -> Vec<Action> {
+  return get_legal_actions(
+    &game_state.my_trees,
+    &game_state.opponent_trees,
+    game_state.sunpoints,
+    &game_state.cells,
+    seedable_cells,
+  );
 }
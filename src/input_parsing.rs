@@ -1,5 +1,7 @@
-use crate::game_logic::{Cell, Tree};
-use std::io;
+use crate::game_logic::{Cell, GameState, Tree};
+use crate::log;
+use crate::logging::{Category, Level};
+use std::io::BufRead;
 
 pub struct InitialInput {
   pub number_of_cells: i32,
@@ -26,17 +28,17 @@ macro_rules! parse_input {
   };
 }
 
-pub fn parse_initial_input() -> InitialInput {
+pub fn parse_initial_input(input: &mut impl BufRead) -> InitialInput {
   let mut input_line = String::new();
-  io::stdin().read_line(&mut input_line).unwrap();
+  input.read_line(&mut input_line).unwrap();
 
   let number_of_cells = parse_input!(input_line, i32);
 
-  let mut cells: Vec<Cell> = Vec::with_capacity(37);
+  let mut cells: Vec<Cell> = Vec::with_capacity(number_of_cells as usize);
 
   for _ in 0..number_of_cells as usize {
     let mut input_line = String::new();
-    io::stdin().read_line(&mut input_line).unwrap();
+    input.read_line(&mut input_line).unwrap();
 
     let inputs = input_line.split(" ").collect::<Vec<_>>();
     let index = parse_input!(inputs[0], i32); // 0 is the center cell, the next cells spiral outwards
@@ -48,7 +50,9 @@ pub fn parse_initial_input() -> InitialInput {
     let neigh_4 = parse_input!(inputs[6], i32);
     let neigh_5 = parse_input!(inputs[7], i32);
 
-    eprintln!(
+    log!(
+      Category::Parse,
+      Level::Debug,
       "Neighbours for cell {}: {}, {}, {}, {}, {}, {}",
       index, neigh_0, neigh_1, neigh_2, neigh_3, neigh_4, neigh_5
     );
@@ -66,26 +70,26 @@ pub fn parse_initial_input() -> InitialInput {
   };
 }
 
-pub fn parse_turn_input(cells: &Vec<Cell>) -> TurnInput {
+pub fn parse_turn_input(input: &mut impl BufRead, cells: &Vec<Cell>) -> TurnInput {
   let mut input_line = String::new();
-  io::stdin().read_line(&mut input_line).unwrap();
+  input.read_line(&mut input_line).unwrap();
   let day = parse_input!(input_line, i32); // the game lasts 24 days: 0-23
   let mut input_line = String::new();
-  io::stdin().read_line(&mut input_line).unwrap();
+  input.read_line(&mut input_line).unwrap();
   let nutrients = parse_input!(input_line, i32); // the base score you gain from the next COMPLETE action
   let mut input_line = String::new();
-  io::stdin().read_line(&mut input_line).unwrap();
+  input.read_line(&mut input_line).unwrap();
   let inputs = input_line.split(" ").collect::<Vec<_>>();
   let sun = parse_input!(inputs[0], i32); // your sun points
   let score = parse_input!(inputs[1], i32); // your current score
   let mut input_line = String::new();
-  io::stdin().read_line(&mut input_line).unwrap();
+  input.read_line(&mut input_line).unwrap();
   let inputs = input_line.split(" ").collect::<Vec<_>>();
   let opp_sun = parse_input!(inputs[0], i32); // opponent's sun points
   let opp_score = parse_input!(inputs[1], i32); // opponent's score
   let opp_is_waiting = parse_input!(inputs[2], i32); // whether your opponent is asleep until the next day
   let mut input_line = String::new();
-  io::stdin().read_line(&mut input_line).unwrap();
+  input.read_line(&mut input_line).unwrap();
   let number_of_trees = parse_input!(input_line, i32); // the current amount of trees
 
   let mut my_trees: Vec<Tree> = Vec::new();
@@ -93,7 +97,7 @@ pub fn parse_turn_input(cells: &Vec<Cell>) -> TurnInput {
 
   for _ in 0..number_of_trees as usize {
     let mut input_line = String::new();
-    io::stdin().read_line(&mut input_line).unwrap();
+    input.read_line(&mut input_line).unwrap();
     let inputs = input_line.split(" ").collect::<Vec<_>>();
     let cell_index = parse_input!(inputs[0], i32); // location of this tree
     let size = parse_input!(inputs[1], i32); // size of this tree: 0-3
@@ -122,14 +126,14 @@ pub fn parse_turn_input(cells: &Vec<Cell>) -> TurnInput {
   }
 
   let mut input_line = String::new();
-  io::stdin().read_line(&mut input_line).unwrap();
+  input.read_line(&mut input_line).unwrap();
   let number_of_possible_actions = parse_input!(input_line, i32); // all legal actions
 
   let mut possible_actions: Vec<String> = Vec::with_capacity(number_of_possible_actions as usize);
 
   for _ in 0..number_of_possible_actions as usize {
     let mut input_line = String::new();
-    io::stdin().read_line(&mut input_line).unwrap();
+    input.read_line(&mut input_line).unwrap();
     let possible_action = input_line.trim_matches('\n').to_string(); // try printing something from here to start with
 
     possible_actions.push(possible_action);
@@ -151,3 +155,18 @@ pub fn parse_turn_input(cells: &Vec<Cell>) -> TurnInput {
     possible_actions: possible_actions,
   }
 }
+
+pub fn get_game_state(turn_input: TurnInput, cells: &Vec<Cell>) -> GameState {
+  GameState {
+    cells: cells.iter().copied().collect(),
+    day: turn_input.day,
+    score: turn_input.score,
+    nutrients: turn_input.nutrients,
+    sunpoints: turn_input.sunpoints,
+    my_trees: turn_input.my_trees,
+    opponent_trees: turn_input.opponent_trees,
+    opponent_score: turn_input.opponent_score,
+    opponent_sunpoints: turn_input.opponent_sunpoints,
+    opponent_is_waiting: turn_input.opponent_is_waiting,
+  }
+}
@@ -0,0 +1,77 @@
+use crate::board::Board;
+use crate::game_logic::*;
+use crate::referee::{get_player_state, Match};
+use std::io::{self, Write};
+
+// The text parse_initial_input reads, in the contest format
+pub fn write_initial_input(board: &Board, output: &mut impl Write) -> io::Result<()> {
+  writeln!(output, "{}", board.cells.len())?;
+
+  for cell in board.cells.iter() {
+    let neighbours: Vec<String> = cell
+      .neighbours
+      .iter()
+      .map(|neighbour| neighbour.to_string())
+      .collect();
+    writeln!(
+      output,
+      "{} {} {}",
+      cell.index,
+      cell.richness,
+      neighbours.join(" ")
+    )?;
+  }
+
+  return Ok(());
+}
+
+// The text parse_turn_input reads, as the given player sees the match: for the second player
+// their own trees are the ones marked as mine. It takes the whole Match rather than a
+// GameState, whether the first player waits is only known there and the second player reads
+// it as their opponent's. The trees come in cell order like the referee sends them, and
+// legal_actions are written as orders in the order they are given.
+pub fn write_turn_input(
+  current_match: &Match,
+  is_first_player: bool,
+  legal_actions: &Vec<Action>,
+  output: &mut impl Write,
+) -> io::Result<()> {
+  let game_state = get_player_state(current_match, is_first_player);
+
+  writeln!(output, "{}", game_state.day)?;
+  writeln!(output, "{}", game_state.nutrients)?;
+  writeln!(output, "{} {}", game_state.sunpoints, game_state.score)?;
+  writeln!(
+    output,
+    "{} {} {}",
+    game_state.opponent_sunpoints,
+    game_state.opponent_score,
+    if game_state.opponent_is_waiting { 1 } else { 0 }
+  )?;
+
+  let mut trees: Vec<&Tree> = game_state
+    .my_trees
+    .iter()
+    .chain(game_state.opponent_trees.iter())
+    .collect();
+  trees.sort_by_key(|tree| tree.cell_index);
+
+  writeln!(output, "{}", trees.len())?;
+  for tree in trees {
+    writeln!(
+      output,
+      "{} {} {} {}",
+      tree.cell_index,
+      tree.size,
+      if tree.is_mine { 1 } else { 0 },
+      if tree.is_dormant { 1 } else { 0 }
+    )?;
+  }
+
+  writeln!(output, "{}", legal_actions.len())?;
+  for action in legal_actions.iter() {
+    writeln!(output, "{}", action_to_order(*action))?;
+  }
+
+  return Ok(());
+}
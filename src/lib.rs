@@ -0,0 +1,66 @@
+// The contest code keeps its own style, its lints are allowed there and nowhere else
+#[allow(
+  clippy::len_zero,
+  clippy::match_like_matches_macro,
+)]
+pub mod ai_adhoc;
+#[allow(
+  unused_mut,
+  clippy::assign_op_pattern,
+  clippy::iter_cloned_collect,
+  clippy::len_zero,
+  clippy::match_like_matches_macro,
+  clippy::needless_borrow,
+  clippy::unnecessary_cast,
+  clippy::unnecessary_fold,
+)]
+pub mod ai_bfs;
+#[allow(
+  unused_mut,
+  clippy::assign_op_pattern,
+  clippy::len_zero,
+  clippy::match_like_matches_macro,
+  clippy::needless_borrow,
+  clippy::unnecessary_cast,
+  clippy::unnecessary_fold,
+)]
+pub mod ai_greedy_with_heuristic;
+#[allow(
+  unused_mut,
+  clippy::assign_op_pattern,
+  clippy::match_like_matches_macro,
+  clippy::needless_borrow,
+  clippy::unnecessary_cast,
+  clippy::unnecessary_fold,
+)]
+pub mod ai_greedy_with_simpler_heuristic;
+pub mod ai_rolling_horizon;
+pub mod board;
+pub mod evaluation;
+#[allow(
+  clippy::int_plus_one,
+  clippy::iter_cloned_collect,
+  clippy::iter_kv_map,
+  clippy::len_zero,
+  clippy::useless_format,
+)]
+pub mod game_logic;
+#[allow(
+  clippy::iter_cloned_collect,
+  clippy::match_like_matches_macro,
+)]
+pub mod input_parsing;
+pub mod input_writing;
+pub mod logging;
+pub mod notation;
+pub mod opponent_model;
+pub mod opponent_tracking;
+pub mod perft;
+pub mod referee;
+pub mod render;
+pub mod rng;
+pub mod rules;
+pub mod search_stats;
+pub mod statistics;
+pub mod strategies;
+pub mod transcript;
@@ -0,0 +1,124 @@
+use std::env;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// e.g. BOT_LOG=debug, BOT_LOG=search=debug,timing=off or BOT_LOG=off
+pub const LOG_ENV_VAR: &str = "BOT_LOG";
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub enum Level {
+  Error = 1,
+  Info = 2,
+  Debug = 3,
+  Trace = 4,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Category {
+  Game = 0,
+  Parse = 1,
+  Search = 2,
+  Eval = 3,
+  Timing = 4,
+}
+
+pub const CATEGORIES: [Category; 5] = [
+  Category::Game,
+  Category::Parse,
+  Category::Search,
+  Category::Eval,
+  Category::Timing,
+];
+
+const OFF: u8 = 0;
+
+// highest level printed for each category, indexed by Category
+static MAX_LEVELS: [AtomicU8; 5] = [
+  AtomicU8::new(Level::Info as u8),
+  AtomicU8::new(Level::Info as u8),
+  AtomicU8::new(Level::Info as u8),
+  AtomicU8::new(Level::Info as u8),
+  AtomicU8::new(Level::Info as u8),
+];
+
+// A disabled message costs one relaxed load: the arguments are only formatted once the
+// category and level are known to be enabled
+#[macro_export]
+macro_rules! log {
+  ($category:expr, $level:expr, $($arg:tt)*) => {
+    if $crate::logging::is_enabled($category, $level) {
+      eprintln!($($arg)*);
+    }
+  };
+}
+
+#[inline]
+pub fn is_enabled(category: Category, level: Level) -> bool {
+  return level as u8 <= MAX_LEVELS[category as usize].load(Ordering::Relaxed);
+}
+
+pub fn set_max_level(category: Category, level: Option<Level>) {
+  let max_level = match level {
+    Some(level) => level as u8,
+    None => OFF,
+  };
+
+  MAX_LEVELS[category as usize].store(max_level, Ordering::Relaxed);
+}
+
+pub fn disable_all() {
+  for category in CATEGORIES.iter() {
+    set_max_level(*category, None);
+  }
+}
+
+pub fn init_from_env() {
+  if let Ok(config) = env::var(LOG_ENV_VAR) {
+    configure(&config);
+  }
+}
+
+// Comma-separated "<category>=<level>" entries, a bare level applies to every category
+pub fn configure(config: &str) {
+  for entry in config.split(',').map(|entry| entry.trim()) {
+    let parts: Vec<&str> = entry.split('=').collect();
+
+    match parts.len() {
+      1 => {
+        if let Some(level) = parse_level(parts[0]) {
+          for category in CATEGORIES.iter() {
+            set_max_level(*category, level);
+          }
+        }
+      }
+      2 => {
+        if let (Some(category), Some(level)) = (parse_category(parts[0]), parse_level(parts[1])) {
+          set_max_level(category, level);
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+fn parse_category(name: &str) -> Option<Category> {
+  return match name {
+    "game" => Some(Category::Game),
+    "parse" => Some(Category::Parse),
+    "search" => Some(Category::Search),
+    "eval" => Some(Category::Eval),
+    "timing" => Some(Category::Timing),
+    _ => None,
+  };
+}
+
+// Some(None) turns the category off, None means the level wasn't recognized
+fn parse_level(name: &str) -> Option<Option<Level>> {
+  return match name {
+    "off" => Some(None),
+    "error" => Some(Some(Level::Error)),
+    "info" => Some(Some(Level::Info)),
+    "debug" => Some(Some(Level::Debug)),
+    "trace" => Some(Some(Level::Trace)),
+    _ => None,
+  };
+}
@@ -1,60 +1,98 @@
-mod ai_greedy_with_simpler_heuristic;
-mod game_logic;
-mod input_parsing;
-
-use game_logic::*;
-use input_parsing::*;
-use std::time::SystemTime;
+use codingame_spring_challenge_2021::ai_greedy_with_simpler_heuristic;
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::input_parsing::*;
+use codingame_spring_challenge_2021::log;
+use codingame_spring_challenge_2021::logging::{self, Category, Level};
+use codingame_spring_challenge_2021::opponent_model::*;
+use codingame_spring_challenge_2021::opponent_tracking::*;
+use codingame_spring_challenge_2021::search_stats::*;
+use codingame_spring_challenge_2021::transcript::*;
+use std::io;
 
 fn main() {
-    let initial_input = parse_initial_input();
+    logging::init_from_env();
 
-    let mut seedable_cells: Vec<Vec<Vec<i32>>> = Vec::with_capacity(37);
+    let stdin = io::stdin();
+    let mut input = TranscriptRecorder::from_env(stdin.lock());
 
-    for cell in 0..37 {
-        seedable_cells.push(Vec::with_capacity(3));
+    input.begin_initial_input();
+    let initial_input = parse_initial_input(&mut input);
+    input.end_input();
+    let board = Board::new(initial_input.cells);
 
-        for size in 0..3 {
-            seedable_cells[cell].push(get_seedable_cells_for_cell_and_size(
-                cell,
-                size as i32 + 1,
-                &initial_input.cells,
-            ));
-        }
-    }
+    let mut opponent_tracker = OpponentTracker::new();
+    let mut opponent_model = OpponentModel::new();
+    let mut game_stats = GameStats::new();
+    let mut turn = 0;
 
     // game loop
     loop {
-        let turn_input = parse_turn_input(&initial_input.cells);
+        turn += 1;
+        input.begin_turn(turn);
+        let turn_input = parse_turn_input(&mut input, &board.cells);
+        input.end_input();
+        let mut stats = SearchStats::new(if turn == 1 {
+            FIRST_TURN_TIME_LIMIT
+        } else {
+            TURN_TIME_LIMIT
+        });
+
+        log!(Category::Game, Level::Info, "DAY: {}", turn_input.day);
 
-        eprintln!("DAY: {}", turn_input.day);
+        let opponent_actions = opponent_tracker.observe(&turn_input);
+        if !opponent_actions.is_empty() {
+            let opponent_orders: Vec<String> = opponent_actions
+                .iter()
+                .map(|observed_action| observed_action.observation.to_order())
+                .collect();
+            log!(
+                Category::Game,
+                Level::Info,
+                "Opponent played: {}",
+                opponent_orders.join(", ")
+            );
+            opponent_model.observe_all(&opponent_actions, &board.cells);
+        }
+
+        let current_state = get_game_state(turn_input, &board.cells);
 
-        let current_state = GameState {
-            cells: initial_input.cells.iter().copied().collect(),
-            day: turn_input.day,
-            score: turn_input.score,
-            nutrients: turn_input.nutrients,
-            sunpoints: turn_input.sunpoints,
-            my_trees: turn_input.my_trees,
-            opponent_trees: turn_input.opponent_trees,
-            opponent_score: turn_input.opponent_score,
-        };
+        // only logged, at a level live games don't print by default: not worth the time of a
+        // turn otherwise
+        if !current_state.opponent_is_waiting && logging::is_enabled(Category::Game, Level::Debug) {
+            let opponent_action_distribution =
+                opponent_model.get_action_distribution(&current_state, &board.seedable_cells);
+            log!(
+                Category::Game,
+                Level::Debug,
+                "Opponent most likely to play: {}",
+                action_to_order(get_most_likely_action(&opponent_action_distribution))
+            );
+        }
 
         //let chosen_action = ai_adhoc::get_next_action(current_state, possible_actions);
-        let now = SystemTime::now();
-        let chosen_action =
-            ai_greedy_with_simpler_heuristic::get_next_action(current_state, &seedable_cells);
-        match now.elapsed() {
-            Ok(elapsed) => {
-                // it prints '2'
-                eprintln!("elapsed {}", elapsed.as_millis());
-            }
-            Err(e) => {
-                // an error occurred!
-                eprintln!("Error: {:?}", e);
-            }
+        let day = current_state.day;
+        let chosen_action = ai_greedy_with_simpler_heuristic::get_next_action(
+            current_state,
+            &board.seedable_cells,
+            &mut stats,
+        );
+        stats.finish();
+        log!(Category::Timing, Level::Info, "Search: {}", stats);
+        game_stats.record(turn, day, stats);
+
+        // waiting on the last day ends the game, there is no next turn to print it after
+        if day == board.rules.get_last_day() && chosen_action == Action::Wait {
+            log!(
+                Category::Timing,
+                Level::Info,
+                "{}",
+                game_stats.get_summary(SLOWEST_TURNS_IN_SUMMARY)
+            );
         }
 
-        println!("{}", action_to_order(chosen_action));
+        let order = action_to_order(chosen_action);
+        input.record_order(&order);
+        println!("{}", order);
     }
 }
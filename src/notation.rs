@@ -0,0 +1,144 @@
+use crate::board::Board;
+use crate::game_logic::*;
+
+// One-line notation for a whole position, e.g. "12 18 5:20 3:18:w 0A3,7b1,22B0":
+//   <day> <nutrients> <sun>:<score> <opponent sun>:<opponent score>:<w if waiting, else ->
+//   followed by the trees as <cell><owner><size>, owner A for us and B for the opponent,
+//   lowercase when the tree is dormant, or "-" when there are no trees at all.
+// Our trees come first, each side in the same order as in the GameState.
+impl GameState {
+  pub fn to_notation(&self) -> String {
+    let trees: Vec<String> = self
+      .my_trees
+      .iter()
+      .chain(self.opponent_trees.iter())
+      .map(|tree| {
+        let owner = match (tree.is_mine, tree.is_dormant) {
+          (true, false) => 'A',
+          (true, true) => 'a',
+          (false, false) => 'B',
+          (false, true) => 'b',
+        };
+        format!("{}{}{}", tree.cell_index, owner, tree.size)
+      })
+      .collect();
+
+    return format!(
+      "{} {} {}:{} {}:{}:{} {}",
+      self.day,
+      self.nutrients,
+      self.sunpoints,
+      self.score,
+      self.opponent_sunpoints,
+      self.opponent_score,
+      if self.opponent_is_waiting { "w" } else { "-" },
+      if !trees.is_empty() {
+        trees.join(",")
+      } else {
+        "-".to_string()
+      }
+    );
+  }
+
+  pub fn from_notation(notation: &str, board: &Board) -> Result<GameState, String> {
+    let fields: Vec<&str> = notation.split_whitespace().collect();
+    if fields.len() != 5 {
+      return Err(format!(
+        "Expected 5 fields in notation, got {}",
+        fields.len()
+      ));
+    }
+
+    let my_side: Vec<&str> = fields[2].split(':').collect();
+    let opponent_side: Vec<&str> = fields[3].split(':').collect();
+    if my_side.len() != 2 {
+      return Err(format!("Invalid sun and score: {}", fields[2]));
+    }
+    if opponent_side.len() != 3 {
+      return Err(format!(
+        "Invalid opponent sun, score and waiting flag: {}",
+        fields[3]
+      ));
+    }
+
+    let mut game_state = GameState {
+      cells: board.cells.to_vec(),
+      day: parse_number(fields[0])?,
+      nutrients: parse_number(fields[1])?,
+      sunpoints: parse_number(my_side[0])?,
+      score: parse_number(my_side[1])?,
+      opponent_sunpoints: parse_number(opponent_side[0])?,
+      opponent_score: parse_number(opponent_side[1])?,
+      opponent_is_waiting: match opponent_side[2] {
+        "w" => true,
+        "-" => false,
+        flag => return Err(format!("Invalid waiting flag: {}", flag)),
+      },
+      my_trees: Vec::new(),
+      opponent_trees: Vec::new(),
+    };
+
+    if fields[4] == "-" {
+      return Ok(game_state);
+    }
+
+    for tree_notation in fields[4].split(',') {
+      let tree = parse_tree(tree_notation, board)?;
+      if game_state
+        .my_trees
+        .iter()
+        .chain(game_state.opponent_trees.iter())
+        .any(|other_tree| other_tree.cell_index == tree.cell_index)
+      {
+        return Err(format!("More than one tree on cell {}", tree.cell_index));
+      }
+
+      let trees = if tree.is_mine {
+        &mut game_state.my_trees
+      } else {
+        &mut game_state.opponent_trees
+      };
+      trees.push(tree);
+    }
+
+    return Ok(game_state);
+  }
+}
+
+fn parse_number(field: &str) -> Result<i32, String> {
+  return field
+    .parse::<i32>()
+    .map_err(|_| format!("Invalid number: {}", field));
+}
+
+fn parse_tree(tree_notation: &str, board: &Board) -> Result<Tree, String> {
+  let owner_position = match tree_notation.find(|c: char| c.is_ascii_alphabetic()) {
+    Some(position) => position,
+    None => return Err(format!("Missing owner in tree: {}", tree_notation)),
+  };
+
+  let cell_index = parse_number(&tree_notation[..owner_position])?;
+  let size = parse_number(&tree_notation[owner_position + 1..])?;
+  let (is_mine, is_dormant) = match tree_notation.as_bytes()[owner_position] {
+    b'A' => (true, false),
+    b'a' => (true, true),
+    b'B' => (false, false),
+    b'b' => (false, true),
+    _ => return Err(format!("Invalid owner in tree: {}", tree_notation)),
+  };
+
+  if cell_index < 0 || cell_index as usize >= board.cells.len() {
+    return Err(format!("Invalid cell in tree: {}", tree_notation));
+  }
+  if !(0..=board.rules.get_max_tree_size()).contains(&size) {
+    return Err(format!("Invalid size in tree: {}", tree_notation));
+  }
+
+  return Ok(Tree {
+    cell_index: cell_index,
+    size: size,
+    is_mine: is_mine,
+    is_dormant: is_dormant,
+    cell: board.cells[cell_index as usize],
+  });
+}
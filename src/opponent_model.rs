@@ -0,0 +1,184 @@
+use crate::game_logic::*;
+use crate::opponent_tracking::{Observation, ObservedAction};
+use crate::rng::Random;
+
+// share of each action kind assumed before anything was observed
+const PRIOR_ACTIONS: f32 = 4.0;
+// completing is rare until the opponent shows us when they start harvesting
+const COMPLETION_WEIGHT_BEFORE_FIRST_COMPLETION: f32 = 0.1;
+
+// Per-game tendencies of the opponent, learned from the actions the tracker observed
+pub struct OpponentModel {
+  pub number_of_waits: i32,
+  pub number_of_seeds: i32,
+  pub number_of_grows: i32,
+  pub number_of_completions: i32,
+  pub grows_by_richness: [i32; 4],
+  pub first_completion_day: Option<i32>,
+}
+
+impl Default for OpponentModel {
+  fn default() -> OpponentModel {
+    return OpponentModel::new();
+  }
+}
+
+impl OpponentModel {
+  pub fn new() -> OpponentModel {
+    OpponentModel {
+      number_of_waits: 0,
+      number_of_seeds: 0,
+      number_of_grows: 0,
+      number_of_completions: 0,
+      grows_by_richness: [0; 4],
+      first_completion_day: None,
+    }
+  }
+
+  pub fn observe(&mut self, observed_action: &ObservedAction, cells: &Vec<Cell>) {
+    let action = match observed_action.observation {
+      Observation::Order(action) => action,
+      // where it came from or went doesn't matter, it was a seed
+      Observation::SeedFromUnknownSource(_) | Observation::CancelledSeed(_) => {
+        self.number_of_seeds += 1;
+        return;
+      }
+    };
+
+    match action {
+      Action::Wait => self.number_of_waits += 1,
+      Action::Seed(_, _) => self.number_of_seeds += 1,
+      Action::Grow(target) => {
+        self.number_of_grows += 1;
+        self.grows_by_richness[cells[target as usize].richness as usize] += 1;
+      }
+      Action::Complete(_) => {
+        self.number_of_completions += 1;
+        if self.first_completion_day.is_none() {
+          self.first_completion_day = Some(observed_action.day);
+        }
+      }
+    }
+  }
+
+  // everything the tracker saw since the previous input, in the order it returned them
+  pub fn observe_all(&mut self, observed_actions: &[ObservedAction], cells: &Vec<Cell>) {
+    for observed_action in observed_actions.iter() {
+      self.observe(observed_action, cells);
+    }
+  }
+
+  pub fn get_number_of_actions(&self) -> i32 {
+    return self.number_of_waits
+      + self.number_of_seeds
+      + self.number_of_grows
+      + self.number_of_completions;
+  }
+
+  pub fn get_seed_rate(&self) -> f32 {
+    return self.get_action_rate(self.number_of_seeds);
+  }
+
+  // how much more likely a grow is on a cell of the given richness than on any other
+  pub fn get_grow_priority(&self, richness: i32) -> f32 {
+    let total_grows: i32 = self.grows_by_richness[1..].iter().sum();
+
+    return (self.grows_by_richness[richness as usize] as f32 + 1.0) / (total_grows as f32 + 3.0);
+  }
+
+  // Laplace smoothed, so an empty history gives every kind of action the same weight
+  fn get_action_rate(&self, number_of_actions: i32) -> f32 {
+    return (number_of_actions as f32 + PRIOR_ACTIONS / 4.0)
+      / (self.get_number_of_actions() as f32 + PRIOR_ACTIONS);
+  }
+
+  fn get_completion_weight(&self, day: i32) -> f32 {
+    let completion_rate = self.get_action_rate(self.number_of_completions);
+
+    return match self.first_completion_day {
+      Some(first_completion_day) if day >= first_completion_day => completion_rate,
+      _ => completion_rate * COMPLETION_WEIGHT_BEFORE_FIRST_COMPLETION,
+    };
+  }
+
+  // Probability of each legal opponent action, ordered as returned by get_legal_actions
+  pub fn get_action_distribution(
+    &self,
+    game_state: &GameState,
+    seedable_cells: &Vec<Vec<Vec<i32>>>,
+  ) -> Vec<(Action, f32)> {
+    let legal_actions = get_legal_actions(
+      &game_state.opponent_trees,
+      &game_state.my_trees,
+      game_state.opponent_sunpoints,
+      &game_state.cells,
+      seedable_cells,
+    );
+
+    let number_of_seed_actions = legal_actions
+      .iter()
+      .filter(|action| matches!(action, Action::Seed(_, _)))
+      .count();
+    let total_grow_priority: f32 = legal_actions
+      .iter()
+      .map(|action| match action {
+        Action::Grow(target) => self.get_grow_priority(game_state.cells[*target as usize].richness),
+        _ => 0.0,
+      })
+      .sum();
+    let number_of_complete_actions = legal_actions
+      .iter()
+      .filter(|action| matches!(action, Action::Complete(_)))
+      .count();
+
+    let weights: Vec<f32> = legal_actions
+      .iter()
+      .map(|action| match action {
+        Action::Wait => self.get_action_rate(self.number_of_waits),
+        Action::Seed(_, _) => self.get_seed_rate() / number_of_seed_actions as f32,
+        Action::Grow(target) => {
+          self.get_action_rate(self.number_of_grows)
+            * self.get_grow_priority(game_state.cells[*target as usize].richness)
+            / total_grow_priority
+        }
+        Action::Complete(_) => {
+          self.get_completion_weight(game_state.day) / number_of_complete_actions as f32
+        }
+      })
+      .collect();
+
+    let total_weight: f32 = weights.iter().sum();
+
+    return legal_actions
+      .iter()
+      .zip(weights.iter())
+      .map(|(action, weight)| (*action, weight / total_weight))
+      .collect();
+  }
+}
+
+pub fn pick_action(action_distribution: &Vec<(Action, f32)>, random: &mut Random) -> Action {
+  let probabilities: Vec<f32> = action_distribution
+    .iter()
+    .map(|(_, probability)| *probability)
+    .collect();
+
+  return match random.choose_weighted(&probabilities) {
+    Some(index) => action_distribution[index].0,
+    None => Action::Wait,
+  };
+}
+
+pub fn get_most_likely_action(action_distribution: &Vec<(Action, f32)>) -> Action {
+  let mut most_likely_action = Action::Wait;
+  let mut highest_probability = f32::MIN;
+
+  for (action, probability) in action_distribution {
+    if *probability > highest_probability {
+      most_likely_action = *action;
+      highest_probability = *probability;
+    }
+  }
+
+  return most_likely_action;
+}
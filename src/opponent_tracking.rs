@@ -0,0 +1,171 @@
+use crate::game_logic::{action_to_order, Action, Tree};
+use crate::input_parsing::TurnInput;
+
+// An opponent action as far as the inputs show it. Action only holds orders that could be
+// played again, the seeds the inputs only tell half of are kept apart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Observation {
+  Order(Action),
+  // the target of a seed whose source doesn't show: a new day woke every tree up
+  SeedFromUnknownSource(i32),
+  // the source of a seed thrown on the cell we seeded too, which cancelled it
+  CancelledSeed(i32),
+}
+
+impl Observation {
+  // the order itself, None when part of it is unknown
+  pub fn to_action(&self) -> Option<Action> {
+    return match self {
+      Observation::Order(action) => Some(*action),
+      _ => None,
+    };
+  }
+
+  // Like the order, with a ? for the cell that doesn't show
+  pub fn to_order(&self) -> String {
+    return match self {
+      Observation::Order(action) => action_to_order(*action),
+      Observation::SeedFromUnknownSource(target) => format!("SEED ? {}", target),
+      Observation::CancelledSeed(source) => format!("SEED {} ?", source),
+    };
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObservedAction {
+  pub day: i32,
+  pub observation: Observation,
+}
+
+struct ObservedTurn {
+  day: i32,
+  opponent_is_waiting: bool,
+  opponent_trees: Vec<Tree>,
+}
+
+// Rebuilds the opponent's moves by diffing consecutive turn inputs. While we wait the
+// opponent plays on alone, so between two inputs they may have played several actions, all
+// on the day of the first input: a day only ends once both players wait.
+pub struct OpponentTracker {
+  previous_turn: Option<ObservedTurn>,
+  pub history: Vec<ObservedAction>,
+}
+
+impl Default for OpponentTracker {
+  fn default() -> OpponentTracker {
+    return OpponentTracker::new();
+  }
+}
+
+impl OpponentTracker {
+  pub fn new() -> OpponentTracker {
+    OpponentTracker {
+      previous_turn: None,
+      history: Vec::new(),
+    }
+  }
+
+  // The actions the opponent played since the previous input, ending with their WAIT if
+  // they waited. Nothing before the first input.
+  pub fn observe(&mut self, turn_input: &TurnInput) -> Vec<ObservedAction> {
+    let observed_actions: Vec<ObservedAction> = match &self.previous_turn {
+      Some(previous_turn) => infer_opponent_actions(previous_turn, turn_input)
+        .into_iter()
+        .map(|observation| ObservedAction {
+          day: previous_turn.day,
+          observation: observation,
+        })
+        .collect(),
+      None => Vec::new(),
+    };
+    self.history.extend_from_slice(&observed_actions);
+
+    self.previous_turn = Some(ObservedTurn {
+      day: turn_input.day,
+      opponent_is_waiting: turn_input.opponent_is_waiting,
+      opponent_trees: turn_input.opponent_trees.to_vec(),
+    });
+
+    return observed_actions;
+  }
+
+  pub fn get_observations_for_day(&self, day: i32) -> Vec<Observation> {
+    return self
+      .history
+      .iter()
+      .filter(|observed_action| observed_action.day == day)
+      .map(|observed_action| observed_action.observation)
+      .collect();
+  }
+}
+
+// Grows, completions and seeds in no particular order, then the WAIT. The order they were
+// played in doesn't show in the trees.
+fn infer_opponent_actions(
+  previous_turn: &ObservedTurn,
+  turn_input: &TurnInput,
+) -> Vec<Observation> {
+  let mut actions: Vec<Observation> = Vec::new();
+
+  // their WAIT was already seen, they haven't played since
+  if previous_turn.opponent_is_waiting {
+    return actions;
+  }
+
+  // every grow and seed puts a tree to sleep, the ones no grow explains threw a seed. A new
+  // day wakes every tree up, so across days the seed sources are lost.
+  let is_same_day = turn_input.day == previous_turn.day;
+  let mut seed_sources: Vec<i32> = Vec::new();
+
+  for previous_tree in &previous_turn.opponent_trees {
+    match turn_input
+      .opponent_trees
+      .iter()
+      .find(|tree| tree.cell_index == previous_tree.cell_index)
+    {
+      Some(tree) if tree.size > previous_tree.size => {
+        actions.push(Observation::Order(Action::Grow(tree.cell_index)));
+      }
+      // completed, and a seed landed on the cell it left
+      Some(tree) if tree.size < previous_tree.size => {
+        actions.push(Observation::Order(Action::Complete(
+          previous_tree.cell_index,
+        )));
+      }
+      Some(tree) => {
+        if is_same_day && tree.is_dormant && !previous_tree.is_dormant {
+          seed_sources.push(tree.cell_index);
+        }
+      }
+      None => actions.push(Observation::Order(Action::Complete(
+        previous_tree.cell_index,
+      ))),
+    }
+  }
+
+  for tree in &turn_input.opponent_trees {
+    let is_new_tree = !previous_turn.opponent_trees.iter().any(|previous_tree| {
+      previous_tree.cell_index == tree.cell_index && previous_tree.size <= tree.size
+    });
+
+    if is_new_tree {
+      // which sleeping tree threw which seed can't be told apart
+      actions.push(if seed_sources.is_empty() {
+        Observation::SeedFromUnknownSource(tree.cell_index)
+      } else {
+        Observation::Order(Action::Seed(seed_sources.remove(0), tree.cell_index))
+      });
+    }
+  }
+
+  // a seed thrown on the cell we seeded too is cancelled, its source still falls asleep
+  for source in seed_sources {
+    actions.push(Observation::CancelledSeed(source));
+  }
+
+  if !is_same_day || turn_input.opponent_is_waiting {
+    actions.push(Observation::Order(Action::Wait));
+  }
+
+  return actions;
+}
@@ -0,0 +1,61 @@
+use crate::board::Board;
+use crate::game_logic::*;
+use crate::referee::*;
+
+// Number of joint action sequences (one action per player and turn) reachable in `depth`
+// turns, using the referee's generator and resolver. A finished game counts as one leaf.
+pub fn perft(current_match: &Match, board: &Board, depth: i32) -> u64 {
+  if depth == 0 || is_game_over(current_match) {
+    return 1;
+  }
+
+  let mut number_of_sequences = 0;
+
+  for (first_action, second_action) in get_joint_actions(current_match, board) {
+    let mut next_match = current_match.clone();
+    resolve_turn(&mut next_match, first_action, second_action);
+    number_of_sequences += perft(&next_match, board, depth - 1);
+  }
+
+  return number_of_sequences;
+}
+
+// perft broken down per joint action of the first turn, to find which one diverges
+pub fn perft_divide(
+  current_match: &Match,
+  board: &Board,
+  depth: i32,
+) -> Vec<((Action, Action), u64)> {
+  if depth == 0 || is_game_over(current_match) {
+    return Vec::new();
+  }
+
+  return get_joint_actions(current_match, board)
+    .into_iter()
+    .map(|(first_action, second_action)| {
+      let mut next_match = current_match.clone();
+      resolve_turn(&mut next_match, first_action, second_action);
+      (
+        (first_action, second_action),
+        perft(&next_match, board, depth - 1),
+      )
+    })
+    .collect();
+}
+
+fn get_joint_actions(current_match: &Match, board: &Board) -> Vec<(Action, Action)> {
+  let first_player_actions =
+    get_legal_actions_for_player(current_match, true, &board.seedable_cells);
+  let second_player_actions =
+    get_legal_actions_for_player(current_match, false, &board.seedable_cells);
+
+  let mut joint_actions =
+    Vec::with_capacity(first_player_actions.len() * second_player_actions.len());
+  for first_action in first_player_actions.iter() {
+    for second_action in second_player_actions.iter() {
+      joint_actions.push((*first_action, *second_action));
+    }
+  }
+
+  return joint_actions;
+}
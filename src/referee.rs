@@ -0,0 +1,483 @@
+use crate::board::Board;
+use crate::game_logic::*;
+use crate::rules::RuleSet;
+use crate::search_stats::SearchStats;
+
+// A game between two strategies, kept from the first player's point of view: their trees
+// are my_trees and the second player's are opponent_trees
+#[derive(Clone)]
+pub struct Match {
+  pub game_state: GameState,
+  pub first_player_is_waiting: bool,
+  pub rules: RuleSet,
+}
+
+impl Match {
+  // A match from a position of the first player, who isn't waiting yet: only the opponent's
+  // waiting shows in a GameState
+  pub fn from_game_state(game_state: GameState, board: &Board) -> Match {
+    Match {
+      game_state: game_state,
+      first_player_is_waiting: false,
+      rules: board.rules.clone(),
+    }
+  }
+}
+
+pub struct GameResult {
+  pub first_player_points: i32,
+  pub second_player_points: i32,
+  pub first_player_trees: usize,
+  pub second_player_trees: usize,
+  pub number_of_illegal_actions: [i32; 2],
+}
+
+impl GameResult {
+  // 1 for a first player win, 0.5 for a draw and 0 for a loss, ties broken by trees
+  pub fn get_first_player_result(&self) -> f32 {
+    let points_difference = self.first_player_points - self.second_player_points;
+    let trees_difference = self.first_player_trees as i32 - self.second_player_trees as i32;
+
+    return match (points_difference, trees_difference) {
+      (d, _) if d > 0 => 1.0,
+      (d, _) if d < 0 => 0.0,
+      (_, t) if t > 0 => 1.0,
+      (_, t) if t < 0 => 0.0,
+      _ => 0.5,
+    };
+  }
+}
+
+// A strategy's get_next_action, along with what it carries over between the turns of a game.
+// Each seat of each game gets its own, two players never share a plan.
+pub type Player = Box<dyn FnMut(GameState, &Vec<Vec<Vec<i32>>>, &mut SearchStats) -> Action>;
+
+// A strategy's get_next_action when it has nothing to carry over
+pub type StatelessPlayer = fn(GameState, &Vec<Vec<Vec<i32>>>, &mut SearchStats) -> Action;
+
+pub fn new_match(
+  board: &Board,
+  first_player_trees: &Vec<i32>,
+  second_player_trees: &Vec<i32>,
+) -> Match {
+  let get_trees = |cell_indexes: &Vec<i32>, is_mine: bool| -> Vec<Tree> {
+    cell_indexes
+      .iter()
+      .map(|cell_index| Tree {
+        cell_index: *cell_index,
+        size: 1,
+        is_mine: is_mine,
+        is_dormant: false,
+        cell: board.cells[*cell_index as usize],
+      })
+      .collect()
+  };
+
+  let mut new_match = Match::from_game_state(
+    GameState {
+      cells: board.cells.to_vec(),
+      day: 0,
+      score: 0,
+      nutrients: board.rules.starting_nutrients,
+      sunpoints: 0,
+      my_trees: get_trees(first_player_trees, true),
+      opponent_trees: get_trees(second_player_trees, false),
+      opponent_score: 0,
+      opponent_sunpoints: 0,
+      opponent_is_waiting: false,
+    },
+    board,
+  );
+
+  gather_sun(&mut new_match.game_state);
+
+  return new_match;
+}
+
+pub fn is_game_over(current_match: &Match) -> bool {
+  return current_match.game_state.day > current_match.rules.get_last_day();
+}
+
+// The position as the given player sees it: their trees are my_trees
+pub fn get_player_state(current_match: &Match, is_first_player: bool) -> GameState {
+  return if is_first_player {
+    current_match.game_state.clone()
+  } else {
+    get_swapped_game_state(
+      &current_match.game_state,
+      current_match.first_player_is_waiting,
+    )
+  };
+}
+
+// The position as the second player sees it
+pub fn get_swapped_game_state(game_state: &GameState, first_player_is_waiting: bool) -> GameState {
+  let swap_owner = |tree: &Tree| -> Tree {
+    let mut swapped_tree = *tree;
+    swapped_tree.is_mine = !tree.is_mine;
+    swapped_tree
+  };
+
+  GameState {
+    cells: game_state.cells.to_vec(),
+    day: game_state.day,
+    score: game_state.opponent_score,
+    nutrients: game_state.nutrients,
+    sunpoints: game_state.opponent_sunpoints,
+    my_trees: game_state.opponent_trees.iter().map(swap_owner).collect(),
+    opponent_trees: game_state.my_trees.iter().map(swap_owner).collect(),
+    opponent_score: game_state.score,
+    opponent_sunpoints: game_state.sunpoints,
+    opponent_is_waiting: first_player_is_waiting,
+  }
+}
+
+pub fn get_legal_actions_for_player(
+  current_match: &Match,
+  is_first_player: bool,
+  seedable_cells: &Vec<Vec<Vec<i32>>>,
+) -> Vec<Action> {
+  let game_state = &current_match.game_state;
+  let is_waiting = if is_first_player {
+    current_match.first_player_is_waiting
+  } else {
+    game_state.opponent_is_waiting
+  };
+
+  if is_waiting {
+    return vec![Action::Wait];
+  }
+
+  return if is_first_player {
+    get_legal_actions_with_rules(
+      &game_state.my_trees,
+      &game_state.opponent_trees,
+      game_state.sunpoints,
+      &game_state.cells,
+      seedable_cells,
+      &current_match.rules,
+    )
+  } else {
+    get_legal_actions_with_rules(
+      &game_state.opponent_trees,
+      &game_state.my_trees,
+      game_state.opponent_sunpoints,
+      &game_state.cells,
+      seedable_cells,
+      &current_match.rules,
+    )
+  };
+}
+
+pub fn get_sun_cost(action: Action, trees: &Vec<Tree>, rules: &RuleSet) -> i32 {
+  return match action {
+    Action::Wait => 0,
+    Action::Seed(_, _) => get_sun_cost_to_grow_with_costs(0, trees, &rules.grow_costs),
+    Action::Grow(target) => {
+      let tree = trees.iter().find(|tree| tree.cell_index == target).unwrap();
+      get_sun_cost_to_grow_with_costs(tree.size + 1, trees, &rules.grow_costs)
+    }
+    Action::Complete(_) => rules.completion_cost,
+  };
+}
+
+// Why the player can't play an action, None when it is legal. Meant to be read by whoever
+// typed the order, so it names the first rule the action breaks.
+pub fn get_illegal_action_reason(
+  current_match: &Match,
+  is_first_player: bool,
+  seedable_cells: &Vec<Vec<Vec<i32>>>,
+  action: Action,
+) -> Option<String> {
+  let game_state = &current_match.game_state;
+  let rules = &current_match.rules;
+  let (trees, other_trees, sunpoints, is_waiting) = if is_first_player {
+    (
+      &game_state.my_trees,
+      &game_state.opponent_trees,
+      game_state.sunpoints,
+      current_match.first_player_is_waiting,
+    )
+  } else {
+    (
+      &game_state.opponent_trees,
+      &game_state.my_trees,
+      game_state.opponent_sunpoints,
+      game_state.opponent_is_waiting,
+    )
+  };
+
+  let acting_cell = match action {
+    Action::Wait => return None,
+    Action::Seed(source, _) => source,
+    Action::Grow(target) | Action::Complete(target) => target,
+  };
+
+  if is_waiting {
+    return Some("already waiting for the next day, only WAIT is possible".to_string());
+  }
+
+  let tree = match trees.iter().find(|tree| tree.cell_index == acting_cell) {
+    Some(tree) => tree,
+    None => return Some(format!("no tree of yours on cell {}", acting_cell)),
+  };
+  if tree.is_dormant {
+    return Some(format!(
+      "the tree on cell {} already acted today, it is dormant until tomorrow",
+      acting_cell
+    ));
+  }
+
+  let max_tree_size = rules.get_max_tree_size();
+  match action {
+    Action::Grow(_) if tree.size >= max_tree_size => {
+      return Some(format!(
+        "the tree on cell {} is fully grown, it can only be completed",
+        acting_cell
+      ));
+    }
+    Action::Complete(_) if tree.size < max_tree_size => {
+      return Some(format!(
+        "the tree on cell {} is size {}, only size {} trees can be completed",
+        acting_cell, tree.size, max_tree_size
+      ));
+    }
+    Action::Seed(source, target) => {
+      if tree.size == 0 {
+        return Some(format!("the tree on cell {} is still a seed", source));
+      }
+      if target < 0 || target as usize >= game_state.cells.len() {
+        return Some(format!("there is no cell {}", target));
+      }
+      if !seedable_cells[source as usize][(tree.size - 1) as usize].contains(&target) {
+        return Some(format!(
+          "cell {} is out of reach of the size {} tree on cell {}",
+          target, tree.size, source
+        ));
+      }
+      if game_state.cells[target as usize].richness == 0 {
+        return Some(format!("cell {} is unusable", target));
+      }
+      if trees
+        .iter()
+        .chain(other_trees.iter())
+        .any(|other_tree| other_tree.cell_index == target)
+      {
+        return Some(format!("cell {} already has a tree", target));
+      }
+    }
+    _ => {}
+  }
+
+  let cost = get_sun_cost(action, trees, rules);
+  if cost > sunpoints {
+    return Some(format!(
+      "{} costs {} sun, there is only {}",
+      action_to_order(action),
+      cost,
+      sunpoints
+    ));
+  }
+
+  return None;
+}
+
+// Applies both players' actions at once, the way the referee does: seeds thrown on the same
+// cell cancel each other, and trees completed on the same turn share the same nutrients.
+// Actions must be legal, a player already waiting can only keep waiting.
+pub fn resolve_turn(current_match: &mut Match, first_action: Action, second_action: Action) {
+  let game_state = &mut current_match.game_state;
+
+  let first_cost = get_sun_cost(first_action, &game_state.my_trees, &current_match.rules);
+  let second_cost = get_sun_cost(
+    second_action,
+    &game_state.opponent_trees,
+    &current_match.rules,
+  );
+
+  let seeds_collide = match (first_action, second_action) {
+    (Action::Seed(_, first_target), Action::Seed(_, second_target)) => {
+      first_target == second_target
+    }
+    _ => false,
+  };
+  let nutrients = game_state.nutrients;
+
+  for (action, cost, is_first_player) in [
+    (first_action, first_cost, true),
+    (second_action, second_cost, false),
+  ]
+  .iter()
+  {
+    let (trees, sunpoints, score) = if *is_first_player {
+      (
+        &mut game_state.my_trees,
+        &mut game_state.sunpoints,
+        &mut game_state.score,
+      )
+    } else {
+      (
+        &mut game_state.opponent_trees,
+        &mut game_state.opponent_sunpoints,
+        &mut game_state.opponent_score,
+      )
+    };
+
+    match *action {
+      Action::Wait => {
+        if *is_first_player {
+          current_match.first_player_is_waiting = true;
+        } else {
+          game_state.opponent_is_waiting = true;
+        }
+      }
+      Action::Grow(target) => {
+        let tree = trees
+          .iter_mut()
+          .find(|tree| tree.cell_index == target)
+          .unwrap();
+        tree.size += 1;
+        tree.is_dormant = true;
+        *sunpoints -= cost;
+      }
+      Action::Seed(source, target) => {
+        let source_tree = trees
+          .iter_mut()
+          .find(|tree| tree.cell_index == source)
+          .unwrap();
+        source_tree.is_dormant = true;
+
+        if !seeds_collide {
+          trees.push(Tree {
+            cell_index: target,
+            size: 0,
+            is_mine: *is_first_player,
+            is_dormant: true,
+            cell: game_state.cells[target as usize],
+          });
+          *sunpoints -= cost;
+        }
+      }
+      Action::Complete(target) => {
+        let tree_index = trees
+          .iter()
+          .position(|tree| tree.cell_index == target)
+          .unwrap();
+        *score += get_score_for_cell(nutrients, &trees[tree_index].cell);
+        trees.remove(tree_index);
+        *sunpoints -= cost;
+      }
+    }
+  }
+
+  let number_of_completions = [first_action, second_action]
+    .iter()
+    .filter(|action| matches!(action, Action::Complete(_)))
+    .count() as i32;
+  game_state.nutrients = (game_state.nutrients - number_of_completions).max(0);
+
+  if current_match.first_player_is_waiting && game_state.opponent_is_waiting {
+    start_next_day(current_match);
+  }
+}
+
+fn start_next_day(current_match: &mut Match) {
+  let game_state = &mut current_match.game_state;
+
+  game_state.day += 1;
+  current_match.first_player_is_waiting = false;
+  game_state.opponent_is_waiting = false;
+
+  if game_state.day > current_match.rules.get_last_day() {
+    return;
+  }
+
+  for tree in game_state
+    .my_trees
+    .iter_mut()
+    .chain(game_state.opponent_trees.iter_mut())
+  {
+    tree.is_dormant = false;
+  }
+
+  gather_sun(game_state);
+}
+
+// every tree that isn't in the shadow of a tree at least as big gets its size in sun
+pub fn gather_sun(game_state: &mut GameState) {
+  let shadows = get_shadows_in_field(
+    game_state
+      .my_trees
+      .iter()
+      .chain(game_state.opponent_trees.iter()),
+    game_state.day,
+    &game_state.cells,
+  );
+
+  let get_gathered_sun = |trees: &Vec<Tree>| -> i32 {
+    trees
+      .iter()
+      .filter(|tree| tree.size > shadows[tree.cell_index as usize])
+      .map(|tree| tree.size)
+      .sum()
+  };
+
+  game_state.sunpoints += get_gathered_sun(&game_state.my_trees);
+  game_state.opponent_sunpoints += get_gathered_sun(&game_state.opponent_trees);
+}
+
+// Final points are the score plus a point for every 3 sun left
+pub fn get_game_result(current_match: &Match, number_of_illegal_actions: [i32; 2]) -> GameResult {
+  let game_state = &current_match.game_state;
+
+  GameResult {
+    first_player_points: game_state.score + game_state.sunpoints / 3,
+    second_player_points: game_state.opponent_score + game_state.opponent_sunpoints / 3,
+    first_player_trees: game_state.my_trees.len(),
+    second_player_trees: game_state.opponent_trees.len(),
+    number_of_illegal_actions: number_of_illegal_actions,
+  }
+}
+
+// Plays a whole game in-process, an illegal action is replaced by a WAIT. Nothing is cut
+// short by the clock, so the same players on the same board always play the same game.
+pub fn play_game(
+  board: &Board,
+  first_player_trees: &Vec<i32>,
+  second_player_trees: &Vec<i32>,
+  first_player: Player,
+  second_player: Player,
+) -> GameResult {
+  let mut current_match = new_match(board, first_player_trees, second_player_trees);
+  let mut number_of_illegal_actions = [0; 2];
+  let mut players = [first_player, second_player];
+
+  while !is_game_over(&current_match) {
+    let mut actions = [Action::Wait; 2];
+
+    for (player_index, is_first_player) in [true, false].iter().enumerate() {
+      let legal_actions =
+        get_legal_actions_for_player(&current_match, *is_first_player, &board.seedable_cells);
+      if legal_actions.len() == 1 {
+        continue;
+      }
+
+      let mut stats = SearchStats::without_time_limit();
+      let action = (players[player_index])(
+        get_player_state(&current_match, *is_first_player),
+        &board.seedable_cells,
+        &mut stats,
+      );
+
+      if legal_actions.contains(&action) {
+        actions[player_index] = action;
+      } else {
+        number_of_illegal_actions[player_index] += 1;
+      }
+    }
+
+    resolve_turn(&mut current_match, actions[0], actions[1]);
+  }
+
+  return get_game_result(&current_match, number_of_illegal_actions);
+}
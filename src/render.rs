@@ -0,0 +1,296 @@
+use crate::board::{Board, DIRECTIONS};
+use crate::game_logic::*;
+use std::collections::VecDeque;
+
+// Characters per half cell: cells are 4 characters wide with 2 spaces between them
+const HALF_CELL_WIDTH: i32 = 3;
+
+// Cube coordinates of every cell, found by walking the neighbours from the center so it
+// works for any board we are given, not only the one we generate
+pub fn get_cell_coordinates_from_neighbours(cells: &Vec<Cell>) -> Vec<(i32, i32, i32)> {
+  let mut coordinates: Vec<Option<(i32, i32, i32)>> = vec![None; cells.len()];
+  let mut cells_to_visit: VecDeque<usize> = VecDeque::new();
+
+  coordinates[0] = Some((0, 0, 0));
+  cells_to_visit.push_back(0);
+
+  while let Some(cell_index) = cells_to_visit.pop_front() {
+    let coordinate = coordinates[cell_index].unwrap();
+
+    for (direction, neighbour) in cells[cell_index].neighbours.iter().enumerate() {
+      if *neighbour < 0 || coordinates[*neighbour as usize].is_some() {
+        continue;
+      }
+
+      let offset = DIRECTIONS[direction];
+      coordinates[*neighbour as usize] = Some((
+        coordinate.0 + offset.0,
+        coordinate.1 + offset.1,
+        coordinate.2 + offset.2,
+      ));
+      cells_to_visit.push_back(*neighbour as usize);
+    }
+  }
+
+  return coordinates
+    .iter()
+    .map(|coordinate| coordinate.unwrap())
+    .collect();
+}
+
+// Terminal drawing of the board, two lines per row of cells:
+//   the tree as <owner><size> (A us, B the opponent, lowercase when dormant),
+//   ".." for an empty cell or "##" for an unusable one,
+//   then the richness of the cell.
+pub fn ascii(game_state: &GameState, board: &Board) -> String {
+  return render_ascii(game_state, board, None);
+}
+
+// Same as ascii, with the size of the shadow on each cell for the given day (e.g. "3~2")
+pub fn ascii_with_shadows(game_state: &GameState, board: &Board, day: i32) -> String {
+  return render_ascii(game_state, board, Some(day));
+}
+
+fn render_ascii(game_state: &GameState, board: &Board, shadow_day: Option<i32>) -> String {
+  let coordinates = get_cell_coordinates_from_neighbours(&board.cells);
+  let radius = coordinates
+    .iter()
+    .map(|coordinate| coordinate.2.abs())
+    .max()
+    .unwrap();
+
+  let shadows = shadow_day.map(|day| {
+    get_shadows_in_field(
+      game_state
+        .my_trees
+        .iter()
+        .chain(game_state.opponent_trees.iter()),
+      day,
+      &board.cells,
+    )
+  });
+
+  let mut rendered = String::new();
+
+  // z grows downwards, so the rows go from the top of the board to the bottom
+  for row in -radius..=radius {
+    let mut cells_in_row: Vec<(i32, usize)> = coordinates
+      .iter()
+      .enumerate()
+      .filter(|(_, coordinate)| coordinate.2 == row)
+      .map(|(cell_index, coordinate)| (2 * coordinate.0 + coordinate.2, cell_index))
+      .collect();
+    cells_in_row.sort();
+
+    let mut tree_line = String::new();
+    let mut richness_line = String::new();
+
+    for (column, cell_index) in cells_in_row {
+      let position = ((column + 2 * radius) * HALF_CELL_WIDTH) as usize;
+      let cell = &board.cells[cell_index];
+
+      let tree_text = if cell.richness == 0 {
+        "##".to_string()
+      } else {
+        match game_state
+          .my_trees
+          .iter()
+          .chain(game_state.opponent_trees.iter())
+          .find(|tree| tree.cell_index == cell_index as i32)
+        {
+          Some(tree) => get_tree_text(tree),
+          None => "..".to_string(),
+        }
+      };
+
+      let richness_text = match &shadows {
+        Some(shadows) if shadows[cell_index] > 0 => {
+          format!("{}~{}", cell.richness, shadows[cell_index])
+        }
+        _ => cell.richness.to_string(),
+      };
+
+      pad_to(&mut tree_line, position);
+      tree_line.push_str(&format!("{:^4}", tree_text));
+      pad_to(&mut richness_line, position);
+      richness_line.push_str(&format!("{:^4}", richness_text));
+    }
+
+    rendered.push_str(tree_line.trim_end());
+    rendered.push('\n');
+    rendered.push_str(richness_line.trim_end());
+    rendered.push('\n');
+  }
+
+  if let Some(day) = shadow_day {
+    rendered.push_str(&format!(
+      "shadows of day {}, cast towards direction {}\n",
+      day,
+      day % 6
+    ));
+  }
+
+  return rendered;
+}
+
+fn get_tree_text(tree: &Tree) -> String {
+  let owner = match (tree.is_mine, tree.is_dormant) {
+    (true, false) => 'A',
+    (true, true) => 'a',
+    (false, false) => 'B',
+    (false, true) => 'b',
+  };
+
+  return format!("{}{}", owner, tree.size);
+}
+
+fn pad_to(line: &mut String, position: usize) {
+  while line.len() < position {
+    line.push(' ');
+  }
+}
+
+// Distance from the center of a hexagon to its corners, in SVG units
+const HEX_SIZE: f64 = 30.0;
+const SVG_MARGIN: f64 = 20.0;
+const SVG_HEADER_HEIGHT: f64 = 30.0;
+
+fn get_richness_colour(richness: i32) -> &'static str {
+  return match richness {
+    0 => "#555555",
+    1 => "#d9c89e",
+    2 => "#a8c66c",
+    _ => "#5e9c3a",
+  };
+}
+
+fn get_tree_radius(size: i32) -> f64 {
+  return match size {
+    0 => 4.0,
+    1 => 8.0,
+    2 => 12.0,
+    _ => 16.0,
+  };
+}
+
+// Self-contained SVG drawing of the position: cells coloured by richness, trees as circles
+// growing with their size, blue for us and red for the opponent, dashed when dormant, and
+// the shadows of the current day darkening the cells they cover
+pub fn svg(game_state: &GameState, board: &Board) -> String {
+  let coordinates = get_cell_coordinates_from_neighbours(&board.cells);
+  let radius = coordinates
+    .iter()
+    .map(|coordinate| coordinate.2.abs())
+    .max()
+    .unwrap() as f64;
+
+  let width = 2.0 * SVG_MARGIN + 3f64.sqrt() * HEX_SIZE * (2.0 * radius + 1.0);
+  let height = 2.0 * SVG_MARGIN + SVG_HEADER_HEIGHT + HEX_SIZE * (3.0 * radius + 2.0);
+  let center = (
+    width / 2.0,
+    SVG_MARGIN + SVG_HEADER_HEIGHT + HEX_SIZE * (1.5 * radius + 1.0),
+  );
+
+  // pointy-topped hexagons, x grows to the right and z downwards
+  let get_cell_center = |cell_index: usize| -> (f64, f64) {
+    let (x, _, z) = coordinates[cell_index];
+    (
+      center.0 + 3f64.sqrt() * HEX_SIZE * (x as f64 + z as f64 / 2.0),
+      center.1 + 1.5 * HEX_SIZE * z as f64,
+    )
+  };
+
+  let shadows = get_shadows_in_field(
+    game_state
+      .my_trees
+      .iter()
+      .chain(game_state.opponent_trees.iter()),
+    game_state.day,
+    &board.cells,
+  );
+
+  let mut rendered = format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"0 0 {:.0} {:.0}\" font-family=\"monospace\">\n",
+    width, height, width, height
+  );
+  rendered.push_str(&format!(
+    "<rect width=\"{:.0}\" height=\"{:.0}\" fill=\"#f4f1e8\"/>\n",
+    width, height
+  ));
+  rendered.push_str(&format!(
+    "<text x=\"{:.0}\" y=\"{:.0}\" font-size=\"12\">day {}, nutrients {}, shadows towards {}</text>\n",
+    SVG_MARGIN,
+    SVG_MARGIN,
+    game_state.day,
+    game_state.nutrients,
+    game_state.day % 6
+  ));
+  rendered.push_str(&format!(
+    "<text x=\"{:.0}\" y=\"{:.0}\" font-size=\"12\"><tspan fill=\"#2b6cb0\">us: sun {} score {}</tspan> <tspan fill=\"#c53030\">opponent: sun {} score {}{}</tspan></text>\n",
+    SVG_MARGIN,
+    SVG_MARGIN + 16.0,
+    game_state.sunpoints,
+    game_state.score,
+    game_state.opponent_sunpoints,
+    game_state.opponent_score,
+    if game_state.opponent_is_waiting { " (waiting)" } else { "" }
+  ));
+
+  for (cell_index, cell) in board.cells.iter().enumerate() {
+    let (x, y) = get_cell_center(cell_index);
+    let corners: Vec<String> = (0..6)
+      .map(|corner| {
+        let angle = (60.0 * corner as f64 - 30.0).to_radians();
+        format!(
+          "{:.1},{:.1}",
+          x + HEX_SIZE * angle.cos(),
+          y + HEX_SIZE * angle.sin()
+        )
+      })
+      .collect();
+    let points = corners.join(" ");
+
+    rendered.push_str(&format!(
+      "<polygon points=\"{}\" fill=\"{}\" stroke=\"#333333\" stroke-width=\"1\"/>\n",
+      points,
+      get_richness_colour(cell.richness)
+    ));
+    if shadows[cell_index] > 0 {
+      rendered.push_str(&format!(
+        "<polygon points=\"{}\" fill=\"#000000\" fill-opacity=\"{:.2}\"/>\n",
+        points,
+        0.12 * shadows[cell_index] as f64
+      ));
+    }
+    rendered.push_str(&format!(
+      "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"9\" fill=\"#222222\" text-anchor=\"middle\">{}</text>\n",
+      x,
+      y + HEX_SIZE * 0.75,
+      cell_index
+    ));
+  }
+
+  for tree in game_state
+    .my_trees
+    .iter()
+    .chain(game_state.opponent_trees.iter())
+  {
+    let (x, y) = get_cell_center(tree.cell_index as usize);
+    rendered.push_str(&format!(
+      "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.0}\" fill=\"{}\" stroke=\"#111111\" stroke-width=\"1.5\"{}/>\n",
+      x,
+      y,
+      get_tree_radius(tree.size),
+      if tree.is_mine { "#2b6cb0" } else { "#c53030" },
+      if tree.is_dormant {
+        " fill-opacity=\"0.5\" stroke-dasharray=\"3,2\""
+      } else {
+        ""
+      }
+    ));
+  }
+
+  rendered.push_str("</svg>\n");
+
+  return rendered;
+}
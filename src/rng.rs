@@ -0,0 +1,118 @@
+// xoshiro256**, seeded through splitmix64. Small, fast and the same on every platform, so a
+// seed is all it takes to play a board, a self-play game or a search again.
+pub struct Random {
+  state: [u64; 4],
+}
+
+impl Random {
+  pub fn new(seed: u64) -> Random {
+    let mut splitmix_state = seed;
+    let mut state = [0; 4];
+
+    for word in state.iter_mut() {
+      splitmix_state = splitmix_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+      let mut z = splitmix_state;
+      z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+      z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+      *word = z ^ (z >> 31);
+    }
+
+    return Random { state: state };
+  }
+
+  pub fn next_u64(&mut self) -> u64 {
+    let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+    let t = self.state[1] << 17;
+
+    self.state[2] ^= self.state[0];
+    self.state[3] ^= self.state[1];
+    self.state[1] ^= self.state[2];
+    self.state[0] ^= self.state[3];
+    self.state[2] ^= t;
+    self.state[3] = self.state[3].rotate_left(45);
+
+    return result;
+  }
+
+  // uniform in [0, 1)
+  pub fn next_f64(&mut self) -> f64 {
+    return (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+  }
+
+  // uniform in [0, bound), without the bias of a plain modulo. An empty range has nothing to
+  // pick from, so a zero bound panics
+  pub fn next_below(&mut self, bound: usize) -> usize {
+    assert!(bound > 0, "next_below needs a positive bound");
+    let bound = bound as u64;
+    let threshold = bound.wrapping_neg() % bound;
+
+    loop {
+      let product = self.next_u64() as u128 * bound as u128;
+      if product as u64 >= threshold {
+        return (product >> 64) as usize;
+      }
+    }
+  }
+
+  // uniform in [low, high], panics when high < low instead of wrapping around
+  pub fn next_in_range(&mut self, low: i32, high: i32) -> i32 {
+    assert!(
+      low <= high,
+      "next_in_range needs low <= high, got {} and {}",
+      low,
+      high
+    );
+    let range_size = (high as i64 - low as i64 + 1) as usize;
+    return (low as i64 + self.next_below(range_size) as i64) as i32;
+  }
+
+  pub fn next_bool(&mut self, probability: f64) -> bool {
+    return self.next_f64() < probability;
+  }
+
+  // Fisher-Yates
+  pub fn shuffle<T>(&mut self, items: &mut [T]) {
+    for i in (1..items.len()).rev() {
+      items.swap(i, self.next_below(i + 1));
+    }
+  }
+
+  pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+    if items.is_empty() {
+      return None;
+    }
+
+    return Some(&items[self.next_below(items.len())]);
+  }
+
+  // Index picked with a probability proportional to its weight, None when no weight is positive
+  pub fn choose_weighted(&mut self, weights: &[f32]) -> Option<usize> {
+    let total_weight: f64 = weights
+      .iter()
+      .filter(|weight| **weight > 0.0)
+      .map(|weight| *weight as f64)
+      .sum();
+    if total_weight <= 0.0 {
+      return None;
+    }
+
+    let roll = self.next_f64() * total_weight;
+    let mut cumulative_weight = 0.0;
+    let mut last_positive_index = None;
+
+    for (index, weight) in weights.iter().enumerate() {
+      if *weight <= 0.0 {
+        continue;
+      }
+
+      cumulative_weight += *weight as f64;
+      last_positive_index = Some(index);
+      if roll < cumulative_weight {
+        return Some(index);
+      }
+    }
+
+    // only reached through rounding
+    return last_positive_index;
+  }
+}
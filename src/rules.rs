@@ -0,0 +1,127 @@
+// The contest rules
+pub const CONTEST_BOARD_RADIUS: i32 = 3;
+pub const CONTEST_NUMBER_OF_DAYS: i32 = 24;
+pub const CONTEST_GROW_COSTS: [i32; 4] = [0, 1, 3, 7];
+pub const CONTEST_COMPLETION_COST: i32 = 4;
+pub const CONTEST_STARTING_NUTRIENTS: i32 = 20;
+pub const CONTEST_SEED_RANGE_PER_SIZE: [i32; 3] = [1, 2, 3];
+pub const CONTEST_MAX_UNUSABLE_CELLS: usize = 10;
+
+pub const STARTING_TREES_PER_PLAYER: usize = 2;
+
+// The largest board rules can ask for, the shadows of a board fit in a fixed size buffer
+pub const MAX_BOARD_RADIUS: i32 = 4;
+pub const MAX_NUMBER_OF_CELLS: usize = (3 * MAX_BOARD_RADIUS * (MAX_BOARD_RADIUS + 1) + 1) as usize;
+
+thread_local! {
+  static CONTEST_RULES: RuleSet = RuleSet::contest();
+}
+
+// The contest rules for the code that reads them on every evaluation, built once instead
+pub fn with_contest_rules<T>(f: impl FnOnce(&RuleSet) -> T) -> T {
+  return CONTEST_RULES.with(|rules| f(rules));
+}
+
+// What the referee and the board generator play by, the contest rules by default. Changing
+// them is for studying the strategies, which are still tuned for the contest: under other
+// rules some of their actions may be illegal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuleSet {
+  pub board_radius: i32,
+  pub number_of_days: i32,
+  // sun to grow a tree into each size, before adding one per own tree of that size.
+  // Index 0 is a seed, the last index is the largest size a tree can reach.
+  pub grow_costs: Vec<i32>,
+  pub completion_cost: i32,
+  pub starting_nutrients: i32,
+  // how far a tree can throw a seed, indexed by [size - 1]
+  pub seed_range_per_size: Vec<i32>,
+  pub max_unusable_cells: usize,
+}
+
+impl Default for RuleSet {
+  fn default() -> RuleSet {
+    return RuleSet::contest();
+  }
+}
+
+impl RuleSet {
+  pub fn contest() -> RuleSet {
+    RuleSet {
+      board_radius: CONTEST_BOARD_RADIUS,
+      number_of_days: CONTEST_NUMBER_OF_DAYS,
+      grow_costs: CONTEST_GROW_COSTS.to_vec(),
+      completion_cost: CONTEST_COMPLETION_COST,
+      starting_nutrients: CONTEST_STARTING_NUTRIENTS,
+      seed_range_per_size: CONTEST_SEED_RANGE_PER_SIZE.to_vec(),
+      max_unusable_cells: CONTEST_MAX_UNUSABLE_CELLS,
+    }
+  }
+
+  // The contest rules on a board of another radius, with as many unusable cells as still
+  // leave room on the outer ring for the starting trees
+  pub fn with_board_radius(board_radius: i32) -> RuleSet {
+    let mut rules = RuleSet::contest();
+    rules.board_radius = board_radius;
+    rules.max_unusable_cells = rules
+      .max_unusable_cells
+      .min(get_outer_ring_size(board_radius).saturating_sub(2 * STARTING_TREES_PER_PLAYER));
+
+    return rules;
+  }
+
+  pub fn get_max_tree_size(&self) -> i32 {
+    return self.grow_costs.len() as i32 - 1;
+  }
+
+  pub fn get_last_day(&self) -> i32 {
+    return self.number_of_days - 1;
+  }
+
+  pub fn get_number_of_cells(&self) -> usize {
+    return (3 * self.board_radius * (self.board_radius + 1) + 1) as usize;
+  }
+
+  pub fn validate(&self) -> Result<(), String> {
+    if self.board_radius < 1 || self.board_radius > MAX_BOARD_RADIUS {
+      return Err(format!(
+        "board radius {} is outside 1 to {}",
+        self.board_radius, MAX_BOARD_RADIUS
+      ));
+    }
+    if self.number_of_days < 1 {
+      return Err(format!("{} days is too short a game", self.number_of_days));
+    }
+    if self.grow_costs.len() < 2 {
+      return Err("trees need at least one size above a seed".to_string());
+    }
+    if self.grow_costs.iter().any(|cost| *cost < 0) || self.completion_cost < 0 {
+      return Err("costs can't be negative".to_string());
+    }
+    if self.seed_range_per_size.len() != self.get_max_tree_size() as usize {
+      return Err(format!(
+        "{} seed ranges for {} tree sizes",
+        self.seed_range_per_size.len(),
+        self.get_max_tree_size()
+      ));
+    }
+    if self.starting_nutrients < 0 {
+      return Err("starting nutrients can't be negative".to_string());
+    }
+    // unusable cells can all land on the outer ring, the starting trees must still fit there
+    if self.max_unusable_cells + 2 * STARTING_TREES_PER_PLAYER
+      > get_outer_ring_size(self.board_radius)
+    {
+      return Err(format!(
+        "{} unusable cells leave no room for the starting trees on a board of radius {}",
+        self.max_unusable_cells, self.board_radius
+      ));
+    }
+
+    return Ok(());
+  }
+}
+
+fn get_outer_ring_size(board_radius: i32) -> usize {
+  return (6 * board_radius.max(0)) as usize;
+}
@@ -0,0 +1,164 @@
+use std::cmp::Reverse;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+// Response time allowed by the referee
+pub const FIRST_TURN_TIME_LIMIT: Duration = Duration::from_millis(1000);
+pub const TURN_TIME_LIMIT: Duration = Duration::from_millis(100);
+
+pub const SLOWEST_TURNS_IN_SUMMARY: usize = 5;
+
+// What a strategy went through to pick one action: filled in by the strategy as it searches,
+// the clock starts when the stats are created
+#[derive(Clone, Copy)]
+pub struct SearchStats {
+  pub nodes_expanded: u64,
+  pub states_evaluated: u64,
+  pub max_depth: i32,
+  pub generation_time: Duration,
+  pub evaluation_time: Duration,
+  pub total_time: Duration,
+  pub deadline_hit: bool,
+  started_at: Instant,
+  time_limit: Option<Duration>,
+}
+
+impl SearchStats {
+  pub fn new(time_limit: Duration) -> SearchStats {
+    return SearchStats::with_time_limit(Some(time_limit));
+  }
+
+  // for the offline tools, where nothing cuts the search short
+  pub fn without_time_limit() -> SearchStats {
+    return SearchStats::with_time_limit(None);
+  }
+
+  fn with_time_limit(time_limit: Option<Duration>) -> SearchStats {
+    SearchStats {
+      nodes_expanded: 0,
+      states_evaluated: 0,
+      max_depth: 0,
+      generation_time: Duration::from_secs(0),
+      evaluation_time: Duration::from_secs(0),
+      total_time: Duration::from_secs(0),
+      deadline_hit: false,
+      started_at: Instant::now(),
+      time_limit: time_limit,
+    }
+  }
+
+  pub fn is_past_deadline(&self) -> bool {
+    return match self.time_limit {
+      Some(time_limit) => self.started_at.elapsed() >= time_limit,
+      None => false,
+    };
+  }
+
+  // for searches that use all the time they get: true once less than the margin is left
+  pub fn is_near_deadline(&self, margin: Duration) -> bool {
+    return match self.time_limit {
+      Some(time_limit) => self.started_at.elapsed() + margin >= time_limit,
+      None => false,
+    };
+  }
+
+  pub fn has_time_limit(&self) -> bool {
+    return self.time_limit.is_some();
+  }
+
+  pub fn record_depth(&mut self, depth: i32) {
+    self.max_depth = self.max_depth.max(depth);
+  }
+
+  // Stops the clock, the deadline counts as hit if the whole turn went over it even when
+  // the search itself didn't notice
+  pub fn finish(&mut self) {
+    self.total_time = self.started_at.elapsed();
+    if let Some(time_limit) = self.time_limit {
+      self.deadline_hit |= self.total_time >= time_limit;
+    }
+  }
+}
+
+impl fmt::Display for SearchStats {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{} nodes, {} evaluated, depth {}, generation {:.1} ms, evaluation {:.1} ms, total {:.1} ms{}",
+      self.nodes_expanded,
+      self.states_evaluated,
+      self.max_depth,
+      self.generation_time.as_secs_f64() * 1000.0,
+      self.evaluation_time.as_secs_f64() * 1000.0,
+      self.total_time.as_secs_f64() * 1000.0,
+      if self.deadline_hit {
+        ", DEADLINE HIT"
+      } else {
+        ""
+      }
+    )
+  }
+}
+
+pub struct TurnStats {
+  pub turn: i32,
+  pub day: i32,
+  pub stats: SearchStats,
+}
+
+// Every turn of a game, for the end-of-game summary
+pub struct GameStats {
+  pub turns: Vec<TurnStats>,
+}
+
+impl Default for GameStats {
+  fn default() -> GameStats {
+    return GameStats::new();
+  }
+}
+
+impl GameStats {
+  pub fn new() -> GameStats {
+    GameStats { turns: Vec::new() }
+  }
+
+  pub fn record(&mut self, turn: i32, day: i32, stats: SearchStats) {
+    self.turns.push(TurnStats {
+      turn: turn,
+      day: day,
+      stats: stats,
+    });
+  }
+
+  pub fn get_slowest_turns(&self, count: usize) -> Vec<&TurnStats> {
+    let mut turns: Vec<&TurnStats> = self.turns.iter().collect();
+    turns.sort_by_key(|turn| Reverse(turn.stats.total_time));
+    turns.truncate(count);
+
+    return turns;
+  }
+
+  pub fn get_summary(&self, number_of_slowest_turns: usize) -> String {
+    let total_time: Duration = self.turns.iter().map(|turn| turn.stats.total_time).sum();
+    let number_of_deadlines_hit = self
+      .turns
+      .iter()
+      .filter(|turn| turn.stats.deadline_hit)
+      .count();
+
+    let mut summary = format!(
+      "{} turns, {:.1} ms in total, {} over the time limit\nslowest turns:",
+      self.turns.len(),
+      total_time.as_secs_f64() * 1000.0,
+      number_of_deadlines_hit
+    );
+    for turn in self.get_slowest_turns(number_of_slowest_turns) {
+      summary.push_str(&format!(
+        "\n  turn {} (day {}): {}",
+        turn.turn, turn.day, turn.stats
+      ));
+    }
+
+    return summary;
+  }
+}
@@ -0,0 +1,38 @@
+// Elo difference implied by a score fraction, clamped so a clean sweep stays finite
+pub fn get_elo_difference(score_fraction: f64) -> f64 {
+  let clamped_score_fraction = score_fraction.clamp(0.001, 0.999);
+
+  return -400.0 * (1.0 / clamped_score_fraction - 1.0).log10();
+}
+
+pub fn get_expected_score(elo_difference: f64) -> f64 {
+  return 1.0 / (1.0 + 10f64.powf(-elo_difference / 400.0));
+}
+
+// Log-likelihood ratio bounds of a sequential probability ratio test: H1 is accepted once the
+// LLR goes above the upper bound and H0 once it goes below the lower one
+pub fn get_sprt_bounds(alpha: f64, beta: f64) -> (f64, f64) {
+  return ((beta / (1.0 - alpha)).ln(), ((1.0 - beta) / alpha).ln());
+}
+
+// Normal approximation of the LLR of H1 (elo1) against H0 (elo0) given the games so far,
+// 0 until the results vary enough to estimate their variance
+pub fn get_log_likelihood_ratio(wins: i32, draws: i32, losses: i32, elo0: f64, elo1: f64) -> f64 {
+  let number_of_games = (wins + draws + losses) as f64;
+  if number_of_games == 0.0 {
+    return 0.0;
+  }
+
+  let win_fraction = wins as f64 / number_of_games;
+  let draw_fraction = draws as f64 / number_of_games;
+  let score = win_fraction + draw_fraction / 2.0;
+  let variance = win_fraction + draw_fraction / 4.0 - score * score;
+  if variance <= 0.0 {
+    return 0.0;
+  }
+
+  let score0 = get_expected_score(elo0);
+  let score1 = get_expected_score(elo1);
+
+  return (score1 - score0) * (2.0 * score - score0 - score1) / (2.0 * variance / number_of_games);
+}
@@ -0,0 +1,86 @@
+use crate::evaluation::EvalBreakdown;
+use crate::game_logic::*;
+use crate::referee::{Player, StatelessPlayer};
+use crate::{
+  ai_adhoc, ai_bfs, ai_greedy_with_heuristic, ai_greedy_with_simpler_heuristic, ai_rolling_horizon,
+};
+
+pub struct Strategy {
+  pub name: &'static str,
+  // a new player for each seat of each game
+  pub new_player: fn() -> Player,
+  // used to explain a decision, None when the strategy doesn't score positions
+  pub simulate_action: Option<fn(&GameState, Action) -> GameState>,
+  pub evaluate_state: Option<fn(&GameState) -> EvalBreakdown>,
+}
+
+// Every strategy the offline tools can pick by name
+pub fn get_strategies() -> Vec<Strategy> {
+  return vec![
+    Strategy {
+      name: "adhoc",
+      // rules rather than a search, there is nothing to count but the time
+      new_player: || {
+        stateless(|game_state, seedable_cells, _| {
+          let possible_actions = get_my_legal_actions(&game_state, seedable_cells);
+          ai_adhoc::get_next_action(game_state, possible_actions)
+        })
+      },
+      simulate_action: None,
+      evaluate_state: None,
+    },
+    Strategy {
+      name: "greedy_with_heuristic",
+      new_player: || {
+        stateless(|game_state, seedable_cells, stats| {
+          let possible_actions = get_my_legal_actions(&game_state, seedable_cells);
+          ai_greedy_with_heuristic::get_next_action(game_state, possible_actions, stats)
+        })
+      },
+      simulate_action: Some(ai_greedy_with_heuristic::simulate_action),
+      evaluate_state: Some(ai_greedy_with_heuristic::evaluate_state),
+    },
+    Strategy {
+      name: "greedy_with_simpler_heuristic",
+      new_player: || stateless(ai_greedy_with_simpler_heuristic::get_next_action),
+      simulate_action: Some(ai_greedy_with_simpler_heuristic::simulate_action),
+      evaluate_state: Some(ai_greedy_with_simpler_heuristic::evaluate_state),
+    },
+    Strategy {
+      name: "bfs",
+      new_player: || stateless(ai_bfs::get_next_action),
+      simulate_action: Some(ai_bfs::simulate_action),
+      evaluate_state: Some(ai_bfs::evaluate_state),
+    },
+    Strategy {
+      name: "bfs_margin",
+      new_player: || stateless(ai_bfs::get_next_action_by_margin),
+      simulate_action: Some(ai_bfs::simulate_action),
+      evaluate_state: Some(ai_bfs::evaluate_state_by_margin),
+    },
+    Strategy {
+      name: "rolling_horizon",
+      new_player: ai_rolling_horizon::new_player,
+      // it scores whole plans over the next days, not single actions
+      simulate_action: None,
+      evaluate_state: None,
+    },
+  ];
+}
+
+fn stateless(get_next_action: StatelessPlayer) -> Player {
+  return Box::new(get_next_action);
+}
+
+pub fn find_strategy(name: &str) -> Option<Strategy> {
+  return get_strategies()
+    .into_iter()
+    .find(|strategy| strategy.name == name);
+}
+
+pub fn get_strategy_names() -> Vec<&'static str> {
+  return get_strategies()
+    .iter()
+    .map(|strategy| strategy.name)
+    .collect();
+}
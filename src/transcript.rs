@@ -0,0 +1,182 @@
+use std::cmp;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, Read, Write};
+
+// Set to a file path, or to "stderr", to record every game we play
+pub const TRANSCRIPT_ENV_VAR: &str = "TRANSCRIPT";
+
+pub const INITIAL_INPUT_MARKER: &str = "#init";
+pub const TURN_MARKER: &str = "#turn";
+pub const ORDER_MARKER: &str = "#order";
+pub const STDERR_BLOCK_START: &str = "=== transcript ===";
+pub const STDERR_BLOCK_END: &str = "=== end transcript ===";
+
+pub enum TranscriptOutput {
+  File(File),
+  Stderr,
+}
+
+// Reads the referee input while copying every consumed line, the turn markers and the
+// orders we print into a transcript that can be fed back to the bot later on
+pub struct TranscriptRecorder<R: BufRead> {
+  input: R,
+  output: Option<TranscriptOutput>,
+  pending: Vec<u8>,
+}
+
+impl<R: BufRead> TranscriptRecorder<R> {
+  pub fn new(input: R, output: Option<TranscriptOutput>) -> TranscriptRecorder<R> {
+    TranscriptRecorder {
+      input: input,
+      output: output,
+      pending: Vec::new(),
+    }
+  }
+
+  pub fn from_env(input: R) -> TranscriptRecorder<R> {
+    let output = match env::var(TRANSCRIPT_ENV_VAR) {
+      Ok(target) if target == "stderr" => Some(TranscriptOutput::Stderr),
+      Ok(path) => Some(TranscriptOutput::File(File::create(path).unwrap())),
+      Err(_) => None,
+    };
+
+    return TranscriptRecorder::new(input, output);
+  }
+
+  pub fn begin_initial_input(&mut self) {
+    self.record_line(INITIAL_INPUT_MARKER);
+  }
+
+  pub fn begin_turn(&mut self, turn: i32) {
+    self.record_line(&format!("{} {}", TURN_MARKER, turn));
+  }
+
+  // Writes out the input read since the last marker, so a turn we never answer, because we
+  // crashed or ran out of time, is still in the transcript
+  pub fn end_input(&mut self) {
+    self.flush();
+  }
+
+  pub fn record_order(&mut self, order: &str) {
+    self.record_line(&format!("{} {}", ORDER_MARKER, order));
+    self.flush();
+  }
+
+  fn record_line(&mut self, line: &str) {
+    if self.output.is_some() {
+      self.pending.extend_from_slice(line.as_bytes());
+      self.pending.push(b'\n');
+    }
+  }
+
+  // stderr is shared with the rest of the debug output, so each input and each order goes in
+  // its own block
+  fn flush(&mut self) {
+    if self.pending.is_empty() {
+      return;
+    }
+
+    match &mut self.output {
+      Some(TranscriptOutput::File(file)) => {
+        file.write_all(&self.pending).unwrap();
+        file.flush().unwrap();
+      }
+      Some(TranscriptOutput::Stderr) => {
+        let stderr = io::stderr();
+        let mut stderr = stderr.lock();
+        writeln!(stderr, "{}", STDERR_BLOCK_START).unwrap();
+        stderr.write_all(&self.pending).unwrap();
+        writeln!(stderr, "{}", STDERR_BLOCK_END).unwrap();
+      }
+      None => {}
+    }
+
+    self.pending.clear();
+  }
+}
+
+impl<R: BufRead> Read for TranscriptRecorder<R> {
+  fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+    let number_of_bytes = {
+      let available = self.fill_buf()?;
+      let number_of_bytes = cmp::min(available.len(), buffer.len());
+      buffer[..number_of_bytes].copy_from_slice(&available[..number_of_bytes]);
+      number_of_bytes
+    };
+
+    self.consume(number_of_bytes);
+    return Ok(number_of_bytes);
+  }
+}
+
+impl<R: BufRead> BufRead for TranscriptRecorder<R> {
+  fn fill_buf(&mut self) -> io::Result<&[u8]> {
+    return self.input.fill_buf();
+  }
+
+  fn consume(&mut self, amount: usize) {
+    if self.output.is_some() {
+      // the inner buffer is still filled, so this hands back the bytes being consumed
+      if let Ok(buffer) = self.input.fill_buf() {
+        self.pending.extend_from_slice(&buffer[..amount]);
+      }
+    }
+
+    self.input.consume(amount);
+  }
+}
+
+pub struct RecordedTurn {
+  pub input: String,
+  pub order: Option<String>,
+}
+
+pub struct Transcript {
+  pub initial_input: String,
+  pub turns: Vec<RecordedTurn>,
+}
+
+// Accepts a transcript file as well as a whole stderr log with transcript blocks in it
+pub fn parse_transcript(text: &str) -> Transcript {
+  let mut transcript = Transcript {
+    initial_input: String::new(),
+    turns: Vec::new(),
+  };
+  let has_stderr_blocks = text.lines().any(|line| line == STDERR_BLOCK_START);
+  let mut is_inside_block = !has_stderr_blocks;
+
+  for line in text.lines() {
+    if line == STDERR_BLOCK_START {
+      is_inside_block = true;
+      continue;
+    }
+    if line == STDERR_BLOCK_END {
+      is_inside_block = false;
+      continue;
+    }
+    if !is_inside_block || line == INITIAL_INPUT_MARKER {
+      continue;
+    }
+
+    if line.starts_with(TURN_MARKER) {
+      transcript.turns.push(RecordedTurn {
+        input: String::new(),
+        order: None,
+      });
+    } else if let Some(order) = line.strip_prefix(ORDER_MARKER) {
+      if let Some(turn) = transcript.turns.last_mut() {
+        turn.order = Some(order.trim().to_string());
+      }
+    } else {
+      let current_input = match transcript.turns.last_mut() {
+        Some(turn) => &mut turn.input,
+        None => &mut transcript.initial_input,
+      };
+      current_input.push_str(line);
+      current_input.push('\n');
+    }
+  }
+
+  return transcript;
+}
@@ -0,0 +1,8 @@
+// Helpers shared by the integration tests
+
+// What a writer of the referee input writes, as text
+pub fn write_to_string(write: impl FnOnce(&mut Vec<u8>) -> std::io::Result<()>) -> String {
+  let mut output: Vec<u8> = Vec::new();
+  write(&mut output).unwrap();
+  return String::from_utf8(output).unwrap();
+}
@@ -0,0 +1,246 @@
+use codingame_spring_challenge_2021::ai_bfs::*;
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::logging;
+use codingame_spring_challenge_2021::search_stats::SearchStats;
+use codingame_spring_challenge_2021::strategies::*;
+use std::fs;
+use std::path::Path;
+
+// Positions the team agrees on, from the files in tests/golden_positions
+struct GoldenPosition {
+  file: String,
+  description: String,
+  notation: String,
+  expectation: Expectation,
+}
+
+enum Expectation {
+  Best(Vec<String>),
+  Never(Vec<String>),
+}
+
+impl Expectation {
+  fn get_orders(&self) -> &Vec<String> {
+    match self {
+      Expectation::Best(orders) => orders,
+      Expectation::Never(orders) => orders,
+    }
+  }
+
+  fn is_met_by(&self, order: &str) -> bool {
+    match self {
+      Expectation::Best(orders) => orders.iter().any(|pattern| matches_order(pattern, order)),
+      Expectation::Never(orders) => !orders.iter().any(|pattern| matches_order(pattern, order)),
+    }
+  }
+}
+
+// "SEED *" matches every seed, anything else has to be the exact order
+fn matches_order(pattern: &str, order: &str) -> bool {
+  match pattern.strip_suffix(" *") {
+    Some(kind) => order.split(' ').next() == Some(kind),
+    None => pattern == order,
+  }
+}
+
+fn read_golden_positions() -> Vec<GoldenPosition> {
+  let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden_positions");
+  let mut files: Vec<_> = fs::read_dir(&directory)
+    .unwrap()
+    .map(|entry| entry.unwrap().path())
+    .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+    .collect();
+  files.sort();
+
+  let mut golden_positions = Vec::new();
+
+  for path in files {
+    let file = path.file_name().unwrap().to_string_lossy().to_string();
+    let text = fs::read_to_string(&path).unwrap();
+
+    // the comment right before a position describes it, the header comment is skipped
+    for block in text.split("\n\n") {
+      let mut description = String::new();
+      let mut notation: Option<String> = None;
+      let mut expectation: Option<Expectation> = None;
+
+      for line in block
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+      {
+        if let Some(comment) = line.strip_prefix('#') {
+          description = comment.trim().to_string();
+        } else if let Some(orders) = line.strip_prefix("best:") {
+          expectation = Some(Expectation::Best(parse_orders(orders)));
+        } else if let Some(orders) = line.strip_prefix("never:") {
+          expectation = Some(Expectation::Never(parse_orders(orders)));
+        } else {
+          notation = Some(line.to_string());
+        }
+      }
+
+      match (notation, expectation) {
+        (Some(notation), Some(expectation)) => golden_positions.push(GoldenPosition {
+          file: file.clone(),
+          description,
+          notation,
+          expectation,
+        }),
+        (None, None) => {}
+        _ => panic!("{}: incomplete position: {}", file, block),
+      }
+    }
+  }
+
+  return golden_positions;
+}
+
+fn parse_orders(orders: &str) -> Vec<String> {
+  return orders
+    .split(',')
+    .map(|order| order.trim().to_string())
+    .collect();
+}
+
+#[test]
+fn golden_positions_are_valid() {
+  let board = get_default_board();
+  let golden_positions = read_golden_positions();
+  assert!(!golden_positions.is_empty());
+
+  for golden_position in golden_positions.iter() {
+    let game_state =
+      GameState::from_notation(&golden_position.notation, &board).unwrap_or_else(|error| {
+        panic!(
+          "{}: {}: {}",
+          golden_position.file, golden_position.notation, error
+        )
+      });
+
+    let legal_orders: Vec<String> = get_my_legal_actions(&game_state, &board.seedable_cells)
+      .into_iter()
+      .map(action_to_order)
+      .collect();
+
+    // a typo in an order would otherwise make a "never" pass forever
+    for pattern in golden_position.expectation.get_orders() {
+      assert!(
+        legal_orders
+          .iter()
+          .any(|order| matches_order(pattern, order)),
+        "{}: {} is not legal in {}",
+        golden_position.file,
+        pattern,
+        golden_position.notation
+      );
+    }
+
+    // and a position where anything goes doesn't test much
+    assert!(
+      legal_orders
+        .iter()
+        .any(|order| !golden_position.expectation.is_met_by(order)),
+      "{}: every legal order passes {}",
+      golden_position.file,
+      golden_position.notation
+    );
+  }
+}
+
+// Pass rates are reported rather than asserted, the strategies don't all get every position
+// right. Run with --nocapture to see them.
+#[test]
+fn strategies_pass_rates() {
+  logging::disable_all();
+
+  let board = get_default_board();
+  let golden_positions = read_golden_positions();
+
+  println!();
+  for strategy in get_strategies() {
+    let mut failures: Vec<String> = Vec::new();
+
+    for golden_position in golden_positions.iter() {
+      let game_state = GameState::from_notation(&golden_position.notation, &board).unwrap();
+      // a new player for each position, they are all different games
+      let mut player = (strategy.new_player)();
+      let order = action_to_order(player(
+        game_state,
+        &board.seedable_cells,
+        &mut SearchStats::without_time_limit(),
+      ));
+
+      if !golden_position.expectation.is_met_by(&order) {
+        failures.push(format!(
+          "  {}: {} ({}) played {}",
+          golden_position.file, golden_position.notation, golden_position.description, order
+        ));
+      }
+    }
+
+    let number_of_passes = golden_positions.len() - failures.len();
+    println!(
+      "{}: {}/{} passed ({:.0}%)",
+      strategy.name,
+      number_of_passes,
+      golden_positions.len(),
+      100.0 * number_of_passes as f64 / golden_positions.len() as f64
+    );
+    for failure in failures {
+      println!("{}", failure);
+    }
+  }
+}
+
+// The margin evaluator once counted every size 3 tree as a completion still to come, so
+// completing never scored more than waiting and it didn't harvest even on the last day
+#[test]
+fn margin_evaluator_prefers_completing_on_the_last_day() {
+  logging::disable_all();
+
+  let board = get_default_board();
+  let game_state = GameState::from_notation("23 20 4:0 0:0:- 0A3,25A1,28B1", &board).unwrap();
+
+  let after_completing = simulate_action(
+    &simulate_action(&game_state, Action::Complete(0)),
+    Action::Wait,
+  );
+  let after_waiting = simulate_action(&game_state, Action::Wait);
+  assert!(
+    evaluate_state_by_margin(&after_completing).total
+      > evaluate_state_by_margin(&after_waiting).total
+  );
+
+  let action = get_next_action_by_margin(
+    game_state,
+    &board.seedable_cells,
+    &mut SearchStats::without_time_limit(),
+  );
+  assert_eq!(action, Action::Complete(0));
+}
+
+// A day 22 plan lands on day 23 after its WAIT, which once looked as over as the game itself:
+// growing a tree to complete it on the last day was worth nothing
+#[test]
+fn margin_evaluator_grows_on_day_22_to_complete_on_the_last_day() {
+  logging::disable_all();
+
+  let board = get_default_board();
+  let game_state = GameState::from_notation("22 20 20:0 0:0:- 0A2,25A1,28B1", &board).unwrap();
+
+  let after_growing = simulate_action(&simulate_action(&game_state, Action::Grow(0)), Action::Wait);
+  let after_waiting = simulate_action(&game_state, Action::Wait);
+  assert_eq!(after_growing.day, 23);
+  assert!(
+    evaluate_state_by_margin(&after_growing).total > evaluate_state_by_margin(&after_waiting).total
+  );
+
+  let action = get_next_action_by_margin(
+    game_state,
+    &board.seedable_cells,
+    &mut SearchStats::without_time_limit(),
+  );
+  assert_eq!(action, Action::Grow(0));
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::input_parsing::*;
+use codingame_spring_challenge_2021::input_writing::*;
+use codingame_spring_challenge_2021::referee::*;
+use codingame_spring_challenge_2021::rng::Random;
+use codingame_spring_challenge_2021::rules::RuleSet;
+use common::write_to_string;
+use std::io::Cursor;
+
+const NUMBER_OF_GAMES: u64 = 20;
+
+// Notation with the trees in cell order, the order the input lists them in
+fn get_sorted_notation(game_state: &GameState) -> String {
+  let mut sorted_state = game_state.clone();
+  sorted_state.my_trees.sort_by_key(|tree| tree.cell_index);
+  sorted_state
+    .opponent_trees
+    .sort_by_key(|tree| tree.cell_index);
+  return sorted_state.to_notation();
+}
+
+fn check_initial_input_round_trip(board: &Board) {
+  let text = write_to_string(|output| write_initial_input(board, output));
+  let initial_input = parse_initial_input(&mut Cursor::new(&text));
+
+  assert_eq!(initial_input.number_of_cells as usize, board.cells.len());
+  for (parsed_cell, cell) in initial_input.cells.iter().zip(board.cells.iter()) {
+    assert_eq!(parsed_cell.index, cell.index);
+    assert_eq!(parsed_cell.richness, cell.richness);
+    assert_eq!(parsed_cell.neighbours, cell.neighbours);
+  }
+
+  // and writing what was parsed gives back the same text
+  let parsed_board = Board::new(initial_input.cells);
+  assert_eq!(
+    write_to_string(|output| write_initial_input(&parsed_board, output)),
+    text
+  );
+}
+
+fn check_turn_input_round_trip(current_match: &Match, board: &Board, is_first_player: bool) {
+  let legal_actions =
+    get_legal_actions_for_player(current_match, is_first_player, &board.seedable_cells);
+  let text = write_to_string(|output| {
+    write_turn_input(current_match, is_first_player, &legal_actions, output)
+  });
+
+  let turn_input = parse_turn_input(&mut Cursor::new(&text), &board.cells);
+  let orders: Vec<String> = legal_actions
+    .iter()
+    .map(|action| action_to_order(*action))
+    .collect();
+  assert_eq!(turn_input.possible_actions, orders, "{}", text);
+
+  let expected_state = get_player_state(current_match, is_first_player);
+  let parsed_state = get_game_state(turn_input, &board.cells);
+  assert_eq!(
+    get_sorted_notation(&parsed_state),
+    get_sorted_notation(&expected_state),
+    "{}",
+    text
+  );
+}
+
+fn check_random_games(rules: &RuleSet) {
+  for seed in 0..NUMBER_OF_GAMES {
+    let setup = generate_game_setup_with_rules(seed, rules);
+    let board = &setup.board;
+    check_initial_input_round_trip(board);
+
+    let mut random = Random::new(seed);
+    let mut current_match = new_match(board, &setup.first_player_trees, &setup.second_player_trees);
+
+    while !is_game_over(&current_match) {
+      check_turn_input_round_trip(&current_match, board, true);
+      check_turn_input_round_trip(&current_match, board, false);
+
+      let mut pick_action = |is_first_player: bool| -> Action {
+        let legal_actions =
+          get_legal_actions_for_player(&current_match, is_first_player, &board.seedable_cells);
+        *random.choose(&legal_actions).unwrap()
+      };
+      let (first_action, second_action) = (pick_action(true), pick_action(false));
+      resolve_turn(&mut current_match, first_action, second_action);
+    }
+  }
+}
+
+#[test]
+fn written_input_parses_back_to_the_same_game() {
+  check_random_games(&RuleSet::contest());
+}
+
+#[test]
+fn written_input_parses_back_on_other_boards() {
+  check_random_games(&RuleSet::with_board_radius(2));
+}
+
+#[test]
+fn turn_input_is_flipped_for_the_second_player() {
+  let board = get_default_board();
+  let current_match = Match::from_game_state(
+    GameState::from_notation("5 18 7:10 4:12:w 0A3,7a2,20B2", &board).unwrap(),
+    &board,
+  );
+
+  let text =
+    write_to_string(|output| write_turn_input(&current_match, false, &vec![Action::Wait], output));
+
+  assert_eq!(
+    text,
+    "5\n18\n4 12\n7 10 0\n3\n0 3 0 0\n7 2 0 1\n20 2 1 0\n1\nWAIT\n"
+  );
+}
@@ -0,0 +1,444 @@
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::referee::*;
+use codingame_spring_challenge_2021::rng::Random;
+use codingame_spring_challenge_2021::rules::RuleSet;
+use std::collections::HashSet;
+
+const NUMBER_OF_GAMES: u64 = 200;
+
+// Far more turns than a game can last, only there so a broken game end fails instead of hanging
+const MAX_TURNS: usize = 2000;
+
+// The trees each player already acted with on the day of a turn, (is_first_player, cell_index)
+type ActedTrees = HashSet<(bool, i32)>;
+
+// Checks one resolved turn, given the match before and after it and the trees that acted
+// earlier that day
+type Invariant = fn(&Match, &Match, (Action, Action), &ActedTrees) -> Result<(), String>;
+
+struct Failure {
+  actions: Vec<(Action, Action)>,
+  message: String,
+}
+
+fn get_acting_tree(action: Action) -> Option<i32> {
+  match action {
+    Action::Wait => None,
+    Action::Seed(source, _) => Some(source),
+    Action::Grow(target) | Action::Complete(target) => Some(target),
+  }
+}
+
+fn check_rules_invariants(
+  previous_match: &Match,
+  next_match: &Match,
+  actions: (Action, Action),
+  acted_trees: &ActedTrees,
+) -> Result<(), String> {
+  let previous_state = &previous_match.game_state;
+  let next_state = &next_match.game_state;
+
+  if next_state.sunpoints < 0 || next_state.opponent_sunpoints < 0 {
+    return Err(format!(
+      "negative sun: {} and {}",
+      next_state.sunpoints, next_state.opponent_sunpoints
+    ));
+  }
+
+  let mut occupied_cells = HashSet::new();
+  for tree in next_state
+    .my_trees
+    .iter()
+    .chain(next_state.opponent_trees.iter())
+  {
+    if !occupied_cells.insert(tree.cell_index) {
+      return Err(format!("two trees on cell {}", tree.cell_index));
+    }
+  }
+
+  if next_state.nutrients > previous_state.nutrients {
+    return Err(format!(
+      "nutrients went from {} to {}",
+      previous_state.nutrients, next_state.nutrients
+    ));
+  }
+
+  if next_state.score < previous_state.score
+    || next_state.opponent_score < previous_state.opponent_score
+  {
+    return Err("a score went down".to_string());
+  }
+
+  for (action, trees) in [
+    (actions.0, &previous_state.my_trees),
+    (actions.1, &previous_state.opponent_trees),
+  ]
+  .iter()
+  {
+    if let Some(cell_index) = get_acting_tree(*action) {
+      if trees
+        .iter()
+        .any(|tree| tree.cell_index == cell_index && tree.is_dormant)
+      {
+        return Err(format!(
+          "dormant tree on cell {} acted again: {}",
+          cell_index,
+          action_to_order(*action)
+        ));
+      }
+    }
+  }
+
+  for (is_first_player, action) in [(true, actions.0), (false, actions.1)].iter() {
+    if let Some(cell_index) = get_acting_tree(*action) {
+      if acted_trees.contains(&(*is_first_player, cell_index)) {
+        return Err(format!(
+          "tree on cell {} acted twice on day {}",
+          cell_index, previous_state.day
+        ));
+      }
+    }
+  }
+
+  if next_state.day > next_match.rules.number_of_days {
+    return Err(format!("game still going on day {}", next_state.day));
+  }
+
+  return Ok(());
+}
+
+// Resolves a turn and checks it, then records which trees acted for the rest of the day
+fn play_turn(
+  current_match: &mut Match,
+  joint_action: (Action, Action),
+  acted_trees: &mut ActedTrees,
+  invariant: Invariant,
+) -> Result<(), String> {
+  let previous_match = current_match.clone();
+  resolve_turn(current_match, joint_action.0, joint_action.1);
+  let result = invariant(&previous_match, current_match, joint_action, acted_trees);
+
+  if current_match.game_state.day != previous_match.game_state.day {
+    acted_trees.clear();
+  } else {
+    for (is_first_player, action) in [(true, joint_action.0), (false, joint_action.1)].iter() {
+      if let Some(cell_index) = get_acting_tree(*action) {
+        acted_trees.insert((*is_first_player, cell_index));
+      }
+    }
+  }
+
+  return result;
+}
+
+// Replays actions from the setup, None when one of them isn't legal anymore: a shrunk
+// sequence that no longer makes sense doesn't reproduce anything
+fn replay(
+  setup: &GameSetup,
+  actions: &[(Action, Action)],
+  invariant: Invariant,
+) -> Option<Result<(), String>> {
+  let mut current_match = new_match(
+    &setup.board,
+    &setup.first_player_trees,
+    &setup.second_player_trees,
+  );
+
+  let mut acted_trees = ActedTrees::new();
+
+  for joint_action in actions.iter() {
+    if is_game_over(&current_match) || !is_legal(&current_match, &setup.board, *joint_action) {
+      return None;
+    }
+
+    if let Err(message) = play_turn(
+      &mut current_match,
+      *joint_action,
+      &mut acted_trees,
+      invariant,
+    ) {
+      return Some(Err(message));
+    }
+  }
+
+  return Some(Ok(()));
+}
+
+fn is_legal(current_match: &Match, board: &Board, actions: (Action, Action)) -> bool {
+  return get_legal_actions_for_player(current_match, true, &board.seedable_cells)
+    .contains(&actions.0)
+    && get_legal_actions_for_player(current_match, false, &board.seedable_cells)
+      .contains(&actions.1);
+}
+
+// Random legal play for both players until the game ends
+fn play_random_game(setup: &GameSetup, seed: u64, invariant: Invariant) -> Result<Match, Failure> {
+  let mut random = Random::new(seed);
+  let mut current_match = new_match(
+    &setup.board,
+    &setup.first_player_trees,
+    &setup.second_player_trees,
+  );
+  let mut actions: Vec<(Action, Action)> = Vec::new();
+  let mut acted_trees = ActedTrees::new();
+
+  while !is_game_over(&current_match) {
+    if actions.len() >= MAX_TURNS {
+      return Err(Failure {
+        actions,
+        message: format!("no end after {} turns", MAX_TURNS),
+      });
+    }
+
+    let mut pick_action = |is_first_player: bool| -> Action {
+      let legal_actions =
+        get_legal_actions_for_player(&current_match, is_first_player, &setup.board.seedable_cells);
+      *random.choose(&legal_actions).unwrap()
+    };
+    let joint_action = (pick_action(true), pick_action(false));
+
+    let result = play_turn(
+      &mut current_match,
+      joint_action,
+      &mut acted_trees,
+      invariant,
+    );
+    actions.push(joint_action);
+
+    if let Err(message) = result {
+      return Err(Failure { actions, message });
+    }
+  }
+
+  return Ok(current_match);
+}
+
+// Drops turns, then turns actions into WAITs, as long as the invariant still breaks
+fn shrink(setup: &GameSetup, failure: Failure, invariant: Invariant) -> Failure {
+  let mut actions = failure.actions;
+  let mut message = failure.message;
+
+  let reproduces = |candidate: &Vec<(Action, Action)>, message: &mut String| -> bool {
+    match replay(setup, candidate, invariant) {
+      Some(Err(candidate_message)) => {
+        *message = candidate_message;
+        true
+      }
+      _ => false,
+    }
+  };
+
+  let mut has_shrunk = true;
+  while has_shrunk {
+    has_shrunk = false;
+
+    let mut i = 0;
+    while i < actions.len() {
+      let mut candidate = actions.clone();
+      candidate.remove(i);
+      if reproduces(&candidate, &mut message) {
+        actions = candidate;
+        has_shrunk = true;
+      } else {
+        i += 1;
+      }
+    }
+
+    for i in 0..actions.len() {
+      for is_first_player in [true, false].iter() {
+        let mut candidate = actions.clone();
+        let action = if *is_first_player {
+          &mut candidate[i].0
+        } else {
+          &mut candidate[i].1
+        };
+        if *action == Action::Wait {
+          continue;
+        }
+        *action = Action::Wait;
+
+        if reproduces(&candidate, &mut message) {
+          actions = candidate;
+          has_shrunk = true;
+        }
+      }
+    }
+  }
+
+  return Failure { actions, message };
+}
+
+fn describe_failure(setup: &GameSetup, failure: &Failure) -> String {
+  let mut current_match = new_match(
+    &setup.board,
+    &setup.first_player_trees,
+    &setup.second_player_trees,
+  );
+  let mut description = format!(
+    "{}\nstart: {}\n",
+    failure.message,
+    current_match.game_state.to_notation()
+  );
+
+  for (first_action, second_action) in failure.actions.iter() {
+    resolve_turn(&mut current_match, *first_action, *second_action);
+    description.push_str(&format!(
+      "{} / {} -> {}\n",
+      action_to_order(*first_action),
+      action_to_order(*second_action),
+      current_match.game_state.to_notation()
+    ));
+  }
+
+  return description;
+}
+
+#[test]
+fn random_games_keep_the_rules_invariants() {
+  for seed in 0..NUMBER_OF_GAMES {
+    let setup = generate_game_setup(seed);
+
+    if let Err(failure) = play_random_game(&setup, seed, check_rules_invariants) {
+      let failure = shrink(&setup, failure, check_rules_invariants);
+      panic!(
+        "board seed {}: {}",
+        seed,
+        describe_failure(&setup, &failure)
+      );
+    }
+  }
+}
+
+#[test]
+fn random_games_end_after_the_last_day() {
+  for seed in 0..NUMBER_OF_GAMES {
+    let setup = generate_game_setup(seed);
+    let final_match = match play_random_game(&setup, seed, check_rules_invariants) {
+      Ok(final_match) => final_match,
+      Err(failure) => panic!("board seed {}: {}", seed, failure.message),
+    };
+
+    // the last WAIT of day 23 ends the game, it doesn't play a day 24
+    assert_eq!(final_match.game_state.day, final_match.rules.number_of_days);
+  }
+}
+
+#[test]
+fn random_games_on_other_rules_keep_the_rules_invariants() {
+  let mut short_game = RuleSet::with_board_radius(2);
+  short_game.number_of_days = 6;
+  let mut expensive_trees = RuleSet::contest();
+  expensive_trees.grow_costs = vec![1, 2, 4, 8, 16];
+  expensive_trees.seed_range_per_size = vec![1, 1, 2, 3];
+  expensive_trees.completion_cost = 6;
+
+  for rules in [RuleSet::with_board_radius(1), short_game, expensive_trees].iter() {
+    assert_eq!(rules.validate(), Ok(()));
+
+    for seed in 0..NUMBER_OF_GAMES / 4 {
+      let setup = generate_game_setup_with_rules(seed, rules);
+      assert_eq!(setup.board.cells.len(), rules.get_number_of_cells());
+
+      match play_random_game(&setup, seed, check_rules_invariants) {
+        Ok(final_match) => assert_eq!(final_match.game_state.day, rules.number_of_days),
+        Err(failure) => {
+          let failure = shrink(&setup, failure, check_rules_invariants);
+          panic!(
+            "{:?}, board seed {}: {}",
+            rules,
+            seed,
+            describe_failure(&setup, &failure)
+          );
+        }
+      }
+    }
+  }
+}
+
+// The shrinker itself, on an invariant random play is sure to break
+fn check_no_tree_of_size_2(
+  _: &Match,
+  next_match: &Match,
+  _: (Action, Action),
+  _: &ActedTrees,
+) -> Result<(), String> {
+  match next_match
+    .game_state
+    .my_trees
+    .iter()
+    .chain(next_match.game_state.opponent_trees.iter())
+    .find(|tree| tree.size >= 2)
+  {
+    Some(tree) => Err(format!(
+      "tree of size {} on cell {}",
+      tree.size, tree.cell_index
+    )),
+    None => Ok(()),
+  }
+}
+
+#[test]
+fn failures_shrink_to_a_minimal_sequence() {
+  let setup = generate_game_setup(1);
+  let failure = play_random_game(&setup, 1, check_no_tree_of_size_2)
+    .err()
+    .unwrap();
+  let number_of_turns = failure.actions.len();
+  let shrunk_failure = shrink(&setup, failure, check_no_tree_of_size_2);
+  let description = describe_failure(&setup, &shrunk_failure);
+
+  // at least a day of sun to afford the grow, then the grow itself
+  assert!(shrunk_failure.actions.len() >= 2, "{}", description);
+  assert!(
+    shrunk_failure.actions.len() < number_of_turns,
+    "{}",
+    description
+  );
+  assert!(matches!(
+    replay(&setup, &shrunk_failure.actions, check_no_tree_of_size_2),
+    Some(Err(_))
+  ));
+
+  // and no single turn can be dropped anymore
+  for i in 0..shrunk_failure.actions.len() {
+    let mut candidate = shrunk_failure.actions.clone();
+    candidate.remove(i);
+    assert!(
+      !matches!(
+        replay(&setup, &candidate, check_no_tree_of_size_2),
+        Some(Err(_))
+      ),
+      "{}",
+      description
+    );
+  }
+}
+
+// A tree acting twice in a day is one of the invariants, so replay and the shrinker see it too
+#[test]
+fn a_tree_acting_twice_in_a_day_breaks_the_invariants() {
+  let setup = generate_game_setup(1);
+  let current_match = new_match(
+    &setup.board,
+    &setup.first_player_trees,
+    &setup.second_player_trees,
+  );
+  let action = get_legal_actions_for_player(&current_match, true, &setup.board.seedable_cells)
+    .into_iter()
+    .find(|action| get_acting_tree(*action).is_some())
+    .unwrap();
+  let cell_index = get_acting_tree(action).unwrap();
+  let joint_action = (action, Action::Wait);
+
+  let mut next_match = current_match.clone();
+  resolve_turn(&mut next_match, joint_action.0, joint_action.1);
+  let mut acted_trees = ActedTrees::new();
+  assert_eq!(
+    check_rules_invariants(&current_match, &next_match, joint_action, &acted_trees),
+    Ok(())
+  );
+
+  acted_trees.insert((true, cell_index));
+  assert!(check_rules_invariants(&current_match, &next_match, joint_action, &acted_trees).is_err());
+}
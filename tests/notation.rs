@@ -0,0 +1,93 @@
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+
+fn check_round_trip(notation: &str) -> GameState {
+  let board = get_default_board();
+  let game_state = GameState::from_notation(notation, &board).unwrap();
+  assert_eq!(game_state.to_notation(), notation);
+
+  return game_state;
+}
+
+fn get_error(notation: &str) -> String {
+  return GameState::from_notation(notation, &get_default_board())
+    .err()
+    .unwrap_or_else(|| panic!("{} was accepted", notation));
+}
+
+#[test]
+fn dormant_trees_round_trip() {
+  let game_state = check_round_trip("12 18 5:20 3:18:- 0A3,5a2,7b1,22B0");
+
+  let dormant_cells: Vec<(i32, bool)> = game_state
+    .my_trees
+    .iter()
+    .chain(game_state.opponent_trees.iter())
+    .map(|tree| (tree.cell_index, tree.is_dormant))
+    .collect();
+  assert_eq!(
+    dormant_cells,
+    vec![(0, false), (5, true), (7, true), (22, false)]
+  );
+  assert_eq!(game_state.my_trees.len(), 2);
+  assert!(game_state.my_trees.iter().all(|tree| tree.is_mine));
+  assert!(game_state.opponent_trees.iter().all(|tree| !tree.is_mine));
+}
+
+#[test]
+fn a_position_without_trees_round_trips() {
+  let game_state = check_round_trip("23 0 0:35 1:27:- -");
+
+  assert!(game_state.my_trees.is_empty());
+  assert!(game_state.opponent_trees.is_empty());
+  assert_eq!((game_state.score, game_state.opponent_score), (35, 27));
+}
+
+#[test]
+fn the_waiting_flag_round_trips() {
+  assert!(check_round_trip("5 20 7:10 4:12:w 0A3,20B2").opponent_is_waiting);
+  assert!(!check_round_trip("5 20 7:10 4:12:- 0A3,20B2").opponent_is_waiting);
+  assert!(check_round_trip("5 20 7:10 4:12:w -").opponent_is_waiting);
+
+  assert_eq!(get_error("5 20 7:10 4:12:x 0A3"), "Invalid waiting flag: x");
+}
+
+#[test]
+fn two_trees_on_the_same_cell_are_rejected() {
+  assert_eq!(
+    get_error("5 20 7:10 4:12:- 0A3,7B1,7a2"),
+    "More than one tree on cell 7"
+  );
+  assert_eq!(
+    get_error("5 20 7:10 4:12:- 0A3,0A3"),
+    "More than one tree on cell 0"
+  );
+}
+
+#[test]
+fn a_bad_owner_is_rejected() {
+  assert_eq!(
+    get_error("5 20 7:10 4:12:- 0A3,7C1"),
+    "Invalid owner in tree: 7C1"
+  );
+  assert_eq!(
+    get_error("5 20 7:10 4:12:- 0A3,71"),
+    "Missing owner in tree: 71"
+  );
+}
+
+#[test]
+fn a_size_out_of_range_is_rejected() {
+  assert_eq!(
+    get_error("5 20 7:10 4:12:- 0A4"),
+    "Invalid size in tree: 0A4"
+  );
+  assert_eq!(
+    get_error("5 20 7:10 4:12:- 0B-1"),
+    "Invalid size in tree: 0B-1"
+  );
+  assert_eq!(
+    get_error("5 20 7:10 4:12:- 37A1"),
+    "Invalid cell in tree: 37A1"
+  );
+}
@@ -0,0 +1,85 @@
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::opponent_model::*;
+use codingame_spring_challenge_2021::opponent_tracking::{Observation, ObservedAction};
+
+// The opponent is B: a size 3 tree they can afford to complete, trees to grow and to seed from
+const POSITIONS: [&str; 3] = [
+  "0 20 2:0 2:0:- 25A1,34A1,28B1,19B1",
+  "11 18 4:10 12:8:- 0A2,25A3,28B3,19B2,1B1",
+  "11 18 4:10 12:8:- 0A2,25A3,28B3,19b2,1B0",
+];
+
+fn get_completion_probability(model: &OpponentModel, game_state: &GameState, board: &Board) -> f32 {
+  return model
+    .get_action_distribution(game_state, &board.seedable_cells)
+    .iter()
+    .filter(|(action, _)| matches!(action, Action::Complete(_)))
+    .map(|(_, probability)| probability)
+    .sum();
+}
+
+fn observe(model: &mut OpponentModel, day: i32, action: Action, board: &Board) {
+  model.observe(
+    &ObservedAction {
+      day: day,
+      observation: Observation::Order(action),
+    },
+    &board.cells,
+  );
+}
+
+#[test]
+fn action_distribution_sums_to_one() {
+  let board = get_default_board();
+  let mut model = OpponentModel::new();
+
+  for observed_action in [Action::Grow(28), Action::Seed(19, 11), Action::Wait].iter() {
+    for notation in POSITIONS.iter() {
+      let game_state = GameState::from_notation(notation, &board).unwrap();
+      let distribution = model.get_action_distribution(&game_state, &board.seedable_cells);
+
+      let total_probability: f32 = distribution
+        .iter()
+        .map(|(_, probability)| probability)
+        .sum();
+      assert!((total_probability - 1.0).abs() < 1e-5, "{}", notation);
+      assert!(distribution
+        .iter()
+        .all(|(_, probability)| *probability >= 0.0));
+    }
+
+    observe(&mut model, 5, *observed_action, &board);
+  }
+}
+
+#[test]
+fn completions_are_likelier_from_the_first_completion_day_on() {
+  let board = get_default_board();
+  let mut model = OpponentModel::new();
+  observe(&mut model, 3, Action::Grow(28), &board);
+  observe(&mut model, 12, Action::Complete(1), &board);
+  observe(&mut model, 15, Action::Complete(2), &board);
+  assert_eq!(model.first_completion_day, Some(12));
+
+  let day_11 = GameState::from_notation(POSITIONS[1], &board).unwrap();
+  let mut day_12 = day_11.clone();
+  day_12.day = 12;
+
+  let completion_probability_before = get_completion_probability(&model, &day_11, &board);
+  let completion_probability_after = get_completion_probability(&model, &day_12, &board);
+  assert!(completion_probability_before > 0.0);
+  assert!(
+    completion_probability_after > 5.0 * completion_probability_before,
+    "{} then {}",
+    completion_probability_before,
+    completion_probability_after
+  );
+
+  // and before anything was completed, no day is any different
+  let model = OpponentModel::new();
+  assert_eq!(
+    get_completion_probability(&model, &day_11, &board),
+    get_completion_probability(&model, &day_12, &board)
+  );
+}
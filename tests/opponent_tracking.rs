@@ -0,0 +1,167 @@
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::input_parsing::*;
+use codingame_spring_challenge_2021::input_writing::*;
+use codingame_spring_challenge_2021::opponent_tracking::*;
+use codingame_spring_challenge_2021::referee::*;
+use std::io::Cursor;
+
+// We are the first player, the opponent is the second
+const POSITION: &str = "5 20 20:0 20:0:- 25A2,34A1,28B3,19B2,1B1";
+
+fn get_match(board: &Board) -> Match {
+  return Match::from_game_state(GameState::from_notation(POSITION, board).unwrap(), board);
+}
+
+fn get_observations(observed_actions: Vec<ObservedAction>) -> Vec<Observation> {
+  return observed_actions
+    .iter()
+    .map(|observed_action| observed_action.observation)
+    .collect();
+}
+
+// What we read at the start of our turn
+fn get_turn_input(current_match: &Match, board: &Board) -> TurnInput {
+  let mut text: Vec<u8> = Vec::new();
+  write_turn_input(current_match, true, &vec![Action::Wait], &mut text).unwrap();
+
+  return parse_turn_input(&mut Cursor::new(text), &board.cells);
+}
+
+fn get_seeds(current_match: &Match, board: &Board, is_first_player: bool) -> Vec<(i32, i32)> {
+  return get_legal_actions_for_player(current_match, is_first_player, &board.seedable_cells)
+    .into_iter()
+    .filter_map(|action| match action {
+      Action::Seed(source, target) => Some((source, target)),
+      _ => None,
+    })
+    .collect();
+}
+
+#[test]
+fn every_action_played_while_we_wait_is_inferred() {
+  let board = get_default_board();
+  let mut current_match = get_match(&board);
+  let mut tracker = OpponentTracker::new();
+  assert!(tracker
+    .observe(&get_turn_input(&current_match, &board))
+    .is_empty());
+
+  // we wait, the opponent plays on alone until they wait too and day 6 starts
+  let (seed_source, seed_target) = get_seeds(&current_match, &board, false)
+    .into_iter()
+    .find(|(source, _)| *source == 1)
+    .unwrap();
+  resolve_turn(&mut current_match, Action::Wait, Action::Grow(19));
+  resolve_turn(&mut current_match, Action::Wait, Action::Complete(28));
+  resolve_turn(
+    &mut current_match,
+    Action::Wait,
+    Action::Seed(seed_source, seed_target),
+  );
+  resolve_turn(&mut current_match, Action::Wait, Action::Wait);
+  assert_eq!(current_match.game_state.day, 6);
+
+  let observed_actions = tracker.observe(&get_turn_input(&current_match, &board));
+  assert!(observed_actions
+    .iter()
+    .all(|observed_action| observed_action.day == 5));
+  let observations = get_observations(observed_actions);
+
+  // the new day woke the source up, which tree threw the seed doesn't show anymore
+  assert_eq!(observations.len(), 4, "{:?}", observations);
+  assert!(
+    observations.contains(&Observation::Order(Action::Grow(19))),
+    "{:?}",
+    observations
+  );
+  assert!(
+    observations.contains(&Observation::Order(Action::Complete(28))),
+    "{:?}",
+    observations
+  );
+  assert!(
+    observations.contains(&Observation::SeedFromUnknownSource(seed_target)),
+    "{:?}",
+    observations
+  );
+  assert_eq!(observations.last(), Some(&Observation::Order(Action::Wait)));
+
+  assert_eq!(tracker.get_observations_for_day(5), observations);
+  let seed = Observation::SeedFromUnknownSource(seed_target);
+  assert_eq!(seed.to_action(), None);
+  assert_eq!(seed.to_order(), format!("SEED ? {}", seed_target));
+}
+
+#[test]
+fn a_seed_cancelled_by_ours_is_inferred_from_its_sleeping_source() {
+  let board = get_default_board();
+  let mut current_match = get_match(&board);
+  let mut tracker = OpponentTracker::new();
+  tracker.observe(&get_turn_input(&current_match, &board));
+
+  let our_seeds = get_seeds(&current_match, &board, true);
+  let (opponent_source, target) = get_seeds(&current_match, &board, false)
+    .into_iter()
+    .find(|(_, target)| our_seeds.iter().any(|(_, our_target)| our_target == target))
+    .unwrap();
+  let our_source = our_seeds
+    .iter()
+    .find(|(_, our_target)| *our_target == target)
+    .unwrap()
+    .0;
+  resolve_turn(
+    &mut current_match,
+    Action::Seed(our_source, target),
+    Action::Seed(opponent_source, target),
+  );
+  assert!(!current_match
+    .game_state
+    .opponent_trees
+    .iter()
+    .any(|tree| tree.cell_index == target));
+
+  let observations = get_observations(tracker.observe(&get_turn_input(&current_match, &board)));
+
+  assert_eq!(
+    observations,
+    vec![Observation::CancelledSeed(opponent_source)]
+  );
+  assert_eq!(observations[0].to_action(), None);
+}
+
+#[test]
+fn a_single_action_on_the_same_day_keeps_its_seed_source() {
+  let board = get_default_board();
+  let mut current_match = get_match(&board);
+  let mut tracker = OpponentTracker::new();
+  tracker.observe(&get_turn_input(&current_match, &board));
+
+  let (source, target) = get_seeds(&current_match, &board, false)[0];
+  resolve_turn(
+    &mut current_match,
+    Action::Grow(34),
+    Action::Seed(source, target),
+  );
+
+  let observations = get_observations(tracker.observe(&get_turn_input(&current_match, &board)));
+  assert_eq!(
+    observations,
+    vec![Observation::Order(Action::Seed(source, target))]
+  );
+  assert_eq!(
+    observations[0].to_action(),
+    Some(Action::Seed(source, target))
+  );
+
+  // their WAIT, then nothing more until the next day
+  resolve_turn(&mut current_match, Action::Grow(25), Action::Wait);
+  assert_eq!(
+    get_observations(tracker.observe(&get_turn_input(&current_match, &board))),
+    vec![Observation::Order(Action::Wait)]
+  );
+  resolve_turn(&mut current_match, Action::Wait, Action::Wait);
+  assert!(tracker
+    .observe(&get_turn_input(&current_match, &board))
+    .is_empty());
+}
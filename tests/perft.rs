@@ -0,0 +1,87 @@
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::perft::*;
+use codingame_spring_challenge_2021::referee::*;
+
+// Both players with two size 1 trees in opposite corners of the outer ring
+fn get_starting_match(board: &Board) -> Match {
+  return new_match(board, &vec![25, 34], &vec![28, 19]);
+}
+
+// Mid-game position with dormant trees, a seed and sun that doesn't cover every grow
+const MIDGAME_POSITION: &str = "5 20 7:0 4:0:- 0A3,7a2,1A1,20B2,30b1,22B0";
+
+fn get_midgame_match(board: &Board) -> Match {
+  return Match::from_game_state(
+    GameState::from_notation(MIDGAME_POSITION, board).unwrap(),
+    board,
+  );
+}
+
+#[test]
+fn perft_from_the_starting_position() {
+  let board = get_default_board();
+  let starting_match = get_starting_match(&board);
+
+  // each side can wait or seed around either tree: (1 + 3 + 3) * (1 + 3 + 3)
+  assert_eq!(perft(&starting_match, &board, 1), 49);
+  assert_eq!(perft(&starting_match, &board, 2), 705);
+  assert_eq!(perft(&starting_match, &board, 3), 6065);
+  assert_eq!(perft(&starting_match, &board, 4), 141045);
+}
+
+#[test]
+fn perft_from_a_midgame_position() {
+  let board = get_default_board();
+  let midgame_match = get_midgame_match(&board);
+
+  // 38 actions for us (wait, complete, grow and 35 seeds) and 9 for the opponent
+  assert_eq!(perft(&midgame_match, &board, 1), 342);
+  assert_eq!(perft(&midgame_match, &board, 2), 8388);
+  assert_eq!(perft(&midgame_match, &board, 3), 347989);
+}
+
+#[test]
+fn perft_divide_adds_up_to_perft() {
+  let board = get_default_board();
+
+  for current_match in [get_starting_match(&board), get_midgame_match(&board)].iter() {
+    let counts = perft_divide(current_match, &board, 2);
+    let total: u64 = counts.iter().map(|(_, count)| count).sum();
+
+    assert_eq!(total, perft(current_match, &board, 2));
+  }
+}
+
+#[test]
+fn perft_divide_of_the_starting_position() {
+  let board = get_default_board();
+  let counts = perft_divide(&get_starting_match(&board), &board, 2);
+
+  let get_count = |first_action: Action, second_action: Action| -> u64 {
+    counts
+      .iter()
+      .find(|(actions, _)| *actions == (first_action, second_action))
+      .unwrap()
+      .1
+  };
+
+  // after both wait, day 1 gives each side 4 sun: wait, 2 grows and 6 seeds
+  assert_eq!(get_count(Action::Wait, Action::Wait), 81);
+  // the waiting player is done for the day, the other one seeds again from its other tree
+  assert_eq!(get_count(Action::Wait, Action::Seed(28, 27)), 4);
+  assert_eq!(get_count(Action::Seed(25, 26), Action::Seed(28, 27)), 16);
+}
+
+#[test]
+fn perft_stops_at_the_end_of_the_game() {
+  let board = get_default_board();
+  let last_turn = Match::from_game_state(
+    GameState::from_notation("23 20 0:0 0:0:w 0A1,1a0", &board).unwrap(),
+    &board,
+  );
+
+  // no sun for anything but waiting, which ends the game
+  assert_eq!(perft(&last_turn, &board, 1), 1);
+  assert_eq!(perft(&last_turn, &board, 3), 1);
+}
@@ -0,0 +1,69 @@
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::render;
+
+// Our dormant size 3 tree in the center, one tree each on the outer ring and an unusable cell
+const POSITION: &str = "3 20 2:0 2:0:- 0a3,25A1,28B2";
+const UNUSABLE_CELL: usize = 36;
+
+const RENDERED: &str = concat!(
+  "          A1    ..    ..    ..\n",
+  "          1     1     1     1\n",
+  "       ..    ..    ..    ..    ..\n",
+  "       1     2     2     2     1\n",
+  "    ..    ..    ..    ..    ..    ..\n",
+  "    1     2     3     3     2     1\n",
+  " B2    ..    ..    a3    ..    ..    ..\n",
+  " 1     2     3     3     3     2     1\n",
+  "    ..    ..    ..    ..    ..    ##\n",
+  "    1     2     3     3     2     0\n",
+  "       ..    ..    ..    ..    ..\n",
+  "       1     2     2     2     1\n",
+  "          ..    ..    ..    ..\n",
+  "          1     1     1     1\n",
+);
+
+// day 3 casts shadows west: the three cells left of the center are in the shadow of a size 3
+const RENDERED_WITH_SHADOWS: &str = concat!(
+  "          A1    ..    ..    ..\n",
+  "          1     1     1     1\n",
+  "       ..    ..    ..    ..    ..\n",
+  "       1     2     2     2     1\n",
+  "    ..    ..    ..    ..    ..    ..\n",
+  "    1     2     3     3     2     1\n",
+  " B2    ..    ..    a3    ..    ..    ..\n",
+  "1~3   2~3   3~3    3     3     2     1\n",
+  "    ..    ..    ..    ..    ..    ##\n",
+  "    1     2     3     3     2     0\n",
+  "       ..    ..    ..    ..    ..\n",
+  "       1     2     2     2     1\n",
+  "          ..    ..    ..    ..\n",
+  "          1     1     1     1\n",
+  "shadows of day 3, cast towards direction 3\n",
+);
+
+fn get_board() -> Board {
+  let mut cells = get_default_board().cells;
+  cells[UNUSABLE_CELL].richness = 0;
+
+  return Board::new(cells);
+}
+
+#[test]
+fn ascii_draws_trees_dormancy_and_unusable_cells() {
+  let board = get_board();
+  let game_state = GameState::from_notation(POSITION, &board).unwrap();
+
+  assert_eq!(render::ascii(&game_state, &board), RENDERED);
+}
+
+#[test]
+fn ascii_with_shadows_annotates_the_shaded_cells() {
+  let board = get_board();
+  let game_state = GameState::from_notation(POSITION, &board).unwrap();
+
+  assert_eq!(
+    render::ascii_with_shadows(&game_state, &board, 3),
+    RENDERED_WITH_SHADOWS
+  );
+}
@@ -0,0 +1,121 @@
+use codingame_spring_challenge_2021::rng::Random;
+
+// From the reference C implementations of splitmix64 and xoshiro256**
+// (https://prng.di.unimi.it): the first outputs of a generator seeded through splitmix64
+const REFERENCE_OUTPUTS: [(u64, [u64; 5]); 2] = [
+  (
+    0,
+    [
+      0x99ec_5f36_cb75_f2b4,
+      0xbf6e_1f78_4956_452a,
+      0x1a5f_849d_4933_e6e0,
+      0x6aa5_94f1_262d_2d2c,
+      0xbba5_ad4a_1f84_2e59,
+    ],
+  ),
+  (
+    2021,
+    [
+      0xf616_12c2_ff4d_9bc1,
+      0x584f_61ab_0b9a_78b4,
+      0x8153_a824_0f70_a3e2,
+      0xf782_5de8_1809_f5f1,
+      0xbfa6_b657_8e1a_9e26,
+    ],
+  ),
+];
+
+#[test]
+fn outputs_match_the_reference_implementation() {
+  for (seed, outputs) in REFERENCE_OUTPUTS.iter() {
+    let mut random = Random::new(*seed);
+    for output in outputs.iter() {
+      assert_eq!(random.next_u64(), *output, "seed {}", seed);
+    }
+  }
+}
+
+#[test]
+fn next_below_covers_the_whole_range_and_nothing_more() {
+  let mut random = Random::new(1);
+  let mut counts = [0; 7];
+  for _ in 0..7000 {
+    counts[random.next_below(counts.len())] += 1;
+  }
+
+  // about 1000 each
+  assert!(counts.iter().all(|count| *count > 800), "{:?}", counts);
+  assert!((0..100).all(|_| random.next_below(1) == 0));
+}
+
+#[test]
+#[should_panic(expected = "positive bound")]
+fn next_below_rejects_an_empty_range() {
+  Random::new(1).next_below(0);
+}
+
+#[test]
+fn next_in_range_includes_both_ends() {
+  let mut random = Random::new(2);
+  let values: Vec<i32> = (0..1000).map(|_| random.next_in_range(-2, 2)).collect();
+
+  assert!(values.iter().all(|value| (-2..=2).contains(value)));
+  assert!(values.contains(&-2) && values.contains(&2));
+  assert_eq!(random.next_in_range(5, 5), 5);
+  let extreme = random.next_in_range(i32::MIN, i32::MAX);
+  assert!((i32::MIN..=i32::MAX).contains(&extreme));
+}
+
+#[test]
+#[should_panic(expected = "low <= high")]
+fn next_in_range_rejects_a_reversed_range() {
+  Random::new(2).next_in_range(3, 2);
+}
+
+#[test]
+fn shuffle_is_a_permutation_that_only_depends_on_the_seed() {
+  let shuffle = |seed: u64| -> Vec<i32> {
+    let mut items: Vec<i32> = (0..20).collect();
+    Random::new(seed).shuffle(&mut items);
+    return items;
+  };
+
+  let mut sorted_items = shuffle(3);
+  assert_ne!(sorted_items, (0..20).collect::<Vec<i32>>());
+  assert_eq!(shuffle(3), sorted_items);
+  assert_ne!(shuffle(4), sorted_items);
+  sorted_items.sort();
+  assert_eq!(sorted_items, (0..20).collect::<Vec<i32>>());
+
+  // nothing to swap
+  let mut random = Random::new(3);
+  let mut no_items: [i32; 0] = [];
+  random.shuffle(&mut no_items);
+  let mut single_item = [7];
+  random.shuffle(&mut single_item);
+  assert_eq!(single_item, [7]);
+}
+
+#[test]
+fn choose_weighted_needs_a_positive_weight() {
+  let mut random = Random::new(5);
+
+  assert_eq!(random.choose_weighted(&[]), None);
+  assert_eq!(random.choose_weighted(&[0.0, 0.0]), None);
+  assert_eq!(random.choose_weighted(&[-1.0, 0.0, -0.5]), None);
+  assert_eq!(random.choose_weighted(&[0.0, -1.0, 0.25]), Some(2));
+}
+
+#[test]
+fn choose_weighted_follows_the_weights() {
+  let mut random = Random::new(6);
+  let weights = [1.0, 0.0, 3.0, -2.0];
+  let mut counts = [0; 4];
+  for _ in 0..4000 {
+    counts[random.choose_weighted(&weights).unwrap()] += 1;
+  }
+
+  // about 1000 and 3000, never a weight that isn't positive
+  assert_eq!(counts[1] + counts[3], 0, "{:?}", counts);
+  assert!(counts[0] > 800 && counts[0] < 1200, "{:?}", counts);
+}
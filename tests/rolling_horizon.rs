@@ -0,0 +1,89 @@
+use codingame_spring_challenge_2021::ai_greedy_with_simpler_heuristic;
+use codingame_spring_challenge_2021::ai_rolling_horizon;
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::logging;
+use codingame_spring_challenge_2021::referee::*;
+use codingame_spring_challenge_2021::search_stats::SearchStats;
+
+fn play_setup(seed: u64) -> GameResult {
+  let setup = generate_game_setup(seed);
+
+  return play_game(
+    &setup.board,
+    &setup.first_player_trees,
+    &setup.second_player_trees,
+    ai_rolling_horizon::new_player(),
+    Box::new(ai_greedy_with_simpler_heuristic::get_next_action),
+  );
+}
+
+// Each game gets new players, what a planner learned and drew in one game mustn't change the
+// next
+#[test]
+fn the_same_game_twice_gives_the_same_result() {
+  logging::disable_all();
+
+  let first_result = play_setup(7);
+  let second_result = play_setup(7);
+
+  assert_eq!(
+    (
+      first_result.first_player_points,
+      first_result.second_player_points,
+      first_result.first_player_trees,
+      first_result.second_player_trees,
+    ),
+    (
+      second_result.first_player_points,
+      second_result.second_player_points,
+      second_result.first_player_trees,
+      second_result.second_player_trees,
+    )
+  );
+}
+
+// Both seats of a game asking for their next action in turns, as when the strategy plays
+// itself: neither carries over the other's plans
+#[test]
+fn two_players_keep_their_own_plans() {
+  logging::disable_all();
+
+  let board = get_default_board();
+  let positions: Vec<GameState> = [
+    "5 20 6:0 6:0:- 25A2,34A1,28B2,19B1",
+    "6 20 9:0 9:0:- 25A2,34A2,28B2,19B1",
+  ]
+  .iter()
+  .map(|notation| GameState::from_notation(notation, &board).unwrap())
+  .collect();
+  let other_position =
+    GameState::from_notation("12 16 14:8 3:6:- 0B3,25B2,28A3,19A1,1A0", &board).unwrap();
+
+  let get_actions = |opponent: Option<&mut Player>| -> Vec<Action> {
+    let mut player = ai_rolling_horizon::new_player();
+    let mut actions = Vec::new();
+    let mut opponent = opponent;
+
+    for game_state in positions.iter() {
+      actions.push(player(
+        game_state.clone(),
+        &board.seedable_cells,
+        &mut SearchStats::without_time_limit(),
+      ));
+      if let Some(opponent) = opponent.as_mut() {
+        opponent(
+          other_position.clone(),
+          &board.seedable_cells,
+          &mut SearchStats::without_time_limit(),
+        );
+      }
+    }
+
+    return actions;
+  };
+
+  let alone = get_actions(None);
+  let mut opponent = ai_rolling_horizon::new_player();
+  assert_eq!(get_actions(Some(&mut opponent)), alone);
+}
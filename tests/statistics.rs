@@ -0,0 +1,34 @@
+use codingame_spring_challenge_2021::statistics::*;
+
+#[test]
+fn sprt_bounds_at_five_percent_errors() {
+  let (lower_bound, upper_bound) = get_sprt_bounds(0.05, 0.05);
+
+  // ln(1 / 19) and ln(19)
+  assert!((lower_bound + 2.944).abs() < 1e-3, "{}", lower_bound);
+  assert!((upper_bound - 2.944).abs() < 1e-3, "{}", upper_bound);
+}
+
+#[test]
+fn log_likelihood_ratio_follows_the_results() {
+  // nothing to estimate the variance from yet
+  assert_eq!(get_log_likelihood_ratio(0, 0, 0, 0.0, 5.0), 0.0);
+  assert_eq!(get_log_likelihood_ratio(10, 0, 0, 0.0, 5.0), 0.0);
+  assert_eq!(get_log_likelihood_ratio(0, 10, 0, 0.0, 5.0), 0.0);
+
+  assert!(get_log_likelihood_ratio(60, 20, 20, 0.0, 5.0) > 0.0);
+  assert!(get_log_likelihood_ratio(20, 20, 60, 0.0, 5.0) < 0.0);
+  // an even record is closer to H0 at 0 than to H1 at 5
+  assert!(get_log_likelihood_ratio(40, 20, 40, 0.0, 5.0) < 0.0);
+}
+
+#[test]
+fn elo_difference_matches_the_expected_score() {
+  assert_eq!(get_elo_difference(0.5), 0.0);
+  assert!((get_elo_difference(0.75) - 190.85).abs() < 0.01);
+  assert!((get_elo_difference(0.25) + 190.85).abs() < 0.01);
+  assert!((get_expected_score(get_elo_difference(0.64)) - 0.64).abs() < 1e-9);
+
+  // a clean sweep stays finite
+  assert!(get_elo_difference(1.0).is_finite() && get_elo_difference(0.0).is_finite());
+}
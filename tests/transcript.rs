@@ -0,0 +1,78 @@
+mod common;
+
+use codingame_spring_challenge_2021::board::*;
+use codingame_spring_challenge_2021::game_logic::*;
+use codingame_spring_challenge_2021::input_parsing::*;
+use codingame_spring_challenge_2021::input_writing::*;
+use codingame_spring_challenge_2021::referee::*;
+use codingame_spring_challenge_2021::transcript::*;
+use common::write_to_string;
+use std::env;
+use std::fs::{self, File};
+use std::io::Cursor;
+
+const NUMBER_OF_TURNS: usize = 12;
+
+// The referee input of the first turns of a game, as the first player reads it, and the
+// orders they answer with
+fn get_game_input(board: &Board, setup: &GameSetup) -> (String, Vec<String>, Vec<String>) {
+  let initial_input = write_to_string(|output| write_initial_input(board, output));
+  let mut turn_inputs: Vec<String> = Vec::new();
+  let mut orders: Vec<String> = Vec::new();
+  let mut current_match = new_match(board, &setup.first_player_trees, &setup.second_player_trees);
+
+  while turn_inputs.len() < NUMBER_OF_TURNS && !is_game_over(&current_match) {
+    let legal_actions = get_legal_actions_for_player(&current_match, true, &board.seedable_cells);
+    turn_inputs.push(write_to_string(|output| {
+      write_turn_input(&current_match, true, &legal_actions, output)
+    }));
+
+    // the last legal action, to play something else than WAIT whenever there is a choice
+    let action = *legal_actions.last().unwrap();
+    orders.push(action_to_order(action));
+    resolve_turn(&mut current_match, action, Action::Wait);
+  }
+
+  return (initial_input, turn_inputs, orders);
+}
+
+#[test]
+fn a_recorded_game_parses_back_to_the_same_input_and_orders() {
+  let setup = generate_game_setup(3);
+  let board = &setup.board;
+  let (initial_input, turn_inputs, orders) = get_game_input(board, &setup);
+  let path = env::temp_dir().join(format!("transcript_test_{}.txt", std::process::id()));
+
+  let mut input = TranscriptRecorder::new(
+    Cursor::new(format!("{}{}", initial_input, turn_inputs.concat())),
+    Some(TranscriptOutput::File(File::create(&path).unwrap())),
+  );
+
+  // the way main reads its input
+  input.begin_initial_input();
+  parse_initial_input(&mut input);
+  input.end_input();
+  for (turn, order) in orders.iter().enumerate() {
+    input.begin_turn(turn as i32 + 1);
+    parse_turn_input(&mut input, &board.cells);
+    input.end_input();
+
+    // what was read is already written before we answer
+    let transcript = parse_transcript(&fs::read_to_string(&path).unwrap());
+    assert_eq!(transcript.turns.len(), turn + 1);
+    assert_eq!(transcript.turns[turn].input, turn_inputs[turn]);
+    assert_eq!(transcript.turns[turn].order, None);
+
+    input.record_order(order);
+  }
+
+  let transcript = parse_transcript(&fs::read_to_string(&path).unwrap());
+  fs::remove_file(&path).unwrap();
+
+  assert_eq!(transcript.initial_input, initial_input);
+  assert_eq!(transcript.turns.len(), turn_inputs.len());
+  for (turn, recorded_turn) in transcript.turns.iter().enumerate() {
+    assert_eq!(recorded_turn.input, turn_inputs[turn]);
+    assert_eq!(recorded_turn.order.as_ref(), Some(&orders[turn]));
+  }
+}